@@ -0,0 +1,42 @@
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use rkt_solver::{alg::random_scramble, solve, solve_cost};
+
+fn bench_solve(c: &mut Criterion) {
+    let mut group = c.benchmark_group("solve");
+    group.sample_size(10);
+    for len in [4, 8, 12, 16] {
+        let alg = random_scramble(len, 0x5eed);
+        group.bench_with_input(BenchmarkId::new("solve", len), &alg, |b, alg| {
+            b.iter(|| solve(black_box(alg)));
+        });
+        group.bench_with_input(BenchmarkId::new("solve_cost", len), &alg, |b, alg| {
+            b.iter(|| solve_cost(black_box(alg)));
+        });
+    }
+    group.finish();
+}
+
+// the scoped threads of `solve` against rayon's global pool, on the same algorithm
+#[cfg(feature = "rayon")]
+fn bench_solve_rayon(c: &mut Criterion) {
+    let mut group = c.benchmark_group("solve_rayon");
+    group.sample_size(10);
+    for len in [8, 14] {
+        let alg = random_scramble(len, 0x5eed);
+        group.bench_with_input(BenchmarkId::new("solve", len), &alg, |b, alg| {
+            b.iter(|| solve(black_box(alg)));
+        });
+        group.bench_with_input(BenchmarkId::new("solve_rayon", len), &alg, |b, alg| {
+            b.iter(|| rkt_solver::solve_rayon(black_box(alg)));
+        });
+    }
+    group.finish();
+}
+
+#[cfg(feature = "rayon")]
+criterion_group!(benches, bench_solve, bench_solve_rayon);
+#[cfg(not(feature = "rayon"))]
+criterion_group!(benches, bench_solve);
+criterion_main!(benches);
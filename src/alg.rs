@@ -0,0 +1,681 @@
+use core::{
+    fmt::{self, Display},
+    ops::Deref,
+    str::FromStr,
+};
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    Metric, MoveOrAxialRot, MoveOrRot,
+    data::{AxialMove, AxialRotation, Axis, Face, Move, Rotation, Z4, parse_amount},
+    facelets::Facelets,
+};
+
+/// The face turns of `recon` as performed while holding the cube in a fixed orientation.
+pub fn flatten(recon: &[MoveOrRot]) -> Vec<Move> {
+    let mut rotation = Rotation::ID;
+    let mut out = vec![];
+    for &v in recon {
+        match v {
+            MoveOrRot::Move(mv) if mv.is_identity() => {}
+            MoveOrRot::Move(mv) => out.push(mv * -rotation),
+            MoveOrRot::Rot(rot) => rotation = rotation * rot,
+        }
+    }
+    out
+}
+
+/// The rotation the cube ends up in after `recon`.
+pub fn net_rotation(recon: &[MoveOrRot]) -> Rotation {
+    recon.iter().fold(Rotation::ID, |acc, v| match v {
+        MoveOrRot::Move(_) => acc,
+        MoveOrRot::Rot(rot) => acc * *rot,
+    })
+}
+
+// merges runs of turns on the same axis, dropping the ones that cancel out. two sequences with the
+// same result are equal up to the relations the solver makes use of
+fn axial_form(alg: impl IntoIterator<Item = Move>) -> Vec<AxialMove> {
+    let mut out: Vec<AxialMove> = vec![];
+    for mv in alg {
+        let mv = AxialMove::from(mv);
+        if mv.is_zero() {
+            continue;
+        }
+
+        match out.last_mut() {
+            Some(last) if last.axis() == mv.axis() => {
+                *last = *last + mv;
+                if last.is_zero() {
+                    out.pop();
+                }
+            }
+            _ => out.push(mv),
+        }
+    }
+    out
+}
+
+pub fn invert(alg: &[Move]) -> Vec<Move> {
+    alg.iter().rev().map(|mv| mv.inv()).collect()
+}
+
+pub fn invert_solution(recon: &[MoveOrRot]) -> Vec<MoveOrRot> {
+    recon
+        .iter()
+        .rev()
+        .map(|v| match *v {
+            MoveOrRot::Move(mv) => MoveOrRot::Move(mv.inv()),
+            MoveOrRot::Rot(rot) => MoveOrRot::Rot(rot.inv()),
+        })
+        .collect()
+}
+
+/// `alg` as seen after rotating the cube by `rot`.
+pub fn rotate_alg(alg: &[Move], rot: Rotation) -> Vec<Move> {
+    alg.iter().map(|&mv| mv * rot).collect()
+}
+
+pub fn rotate_solution(recon: &[MoveOrRot], rot: Rotation) -> Vec<MoveOrRot> {
+    recon
+        .iter()
+        .map(|v| match *v {
+            MoveOrRot::Move(mv) => MoveOrRot::Move(mv * rot),
+            MoveOrRot::Rot(r) => MoveOrRot::Rot(-rot * r * rot),
+        })
+        .collect()
+}
+
+/// `alg` reflected across the plane perpendicular to `axis`, e.g. `R U R'` becomes `L' U' L` for
+/// `Axis::X`.
+pub fn mirror(alg: &[Move], axis: Axis) -> Vec<Move> {
+    alg.iter()
+        .map(|mv| {
+            let face = if mv.axis() == axis {
+                mv.face().opposite()
+            } else {
+                mv.face()
+            };
+            Move::new(face, -mv.by())
+        })
+        .collect()
+}
+
+/// `recon` with each rotation split into its `to_axials`.
+pub fn expand_rotations(recon: &[MoveOrRot]) -> Vec<MoveOrAxialRot> {
+    recon
+        .iter()
+        .flat_map(|&v| match v {
+            MoveOrRot::Move(mv) => vec![MoveOrAxialRot::Move(mv)],
+            MoveOrRot::Rot(rot) => rot.to_axials().map(MoveOrAxialRot::Rot).collect(),
+        })
+        .collect()
+}
+
+/// How many moves the turns of `recon` count as in `metric`, leaving out the rotations.
+pub fn len_in(recon: &[MoveOrRot], metric: Metric) -> usize {
+    recon
+        .iter()
+        .map(|v| match v {
+            MoveOrRot::Move(mv) => metric.cost(mv.by()),
+            MoveOrRot::Rot(_) => 0,
+        })
+        .sum()
+}
+
+pub fn rotations(recon: &[MoveOrRot]) -> usize {
+    recon
+        .iter()
+        .filter(|v| matches!(v, MoveOrRot::Rot(_)))
+        .count()
+}
+
+/// Tags each step of `recon` with whether it can be done without a regrip. Turns are written as
+/// seen from the grip after the rotations before them, and are fine on `R`, `U` and `F`, as well as
+/// `L`, which the left hand turns the way the right hand does `R`. `D` and `B` are the awkward ones.
+/// The rotations between two turns are followed as one change of grip, like `flatten` does: the
+/// first is a regrip unless they add up to nothing, and the rest come with it.
+pub fn annotate_ergonomics(recon: &[MoveOrRot]) -> Vec<(MoveOrRot, bool)> {
+    recon
+        .chunk_by(|a, b| matches!((a, b), (MoveOrRot::Rot(_), MoveOrRot::Rot(_))))
+        .flat_map(|run| {
+            let regrip = net_rotation(run) != Rotation::ID;
+            run.iter().enumerate().map(move |(i, &v)| {
+                let ergonomic = match v {
+                    MoveOrRot::Move(mv) => {
+                        matches!(mv.face(), Face::R | Face::U | Face::F | Face::L)
+                    }
+                    MoveOrRot::Rot(_) => !regrip || i > 0,
+                };
+                (v, ergonomic)
+            })
+        })
+        .collect()
+}
+
+/// Merges adjacent turns on the same axis, bringing turns of the same face together, and drops
+/// the ones that cancel out.
+pub fn normalize(alg: &[Move]) -> Vec<Move> {
+    axial_form(alg.iter().copied())
+        .into_iter()
+        .flat_map(AxialMove::nonzero_moves)
+        .collect()
+}
+
+/// Whether `recon` does the same thing as `alg`, ending in the same orientation.
+pub fn is_equivalent(alg: &[Move], recon: &[MoveOrRot]) -> bool {
+    net_rotation(recon) == Rotation::ID && fingerprint(alg) == fingerprint(&flatten(recon))
+}
+
+/// The position `alg` leaves the cube in, as a number: two algorithms have the same fingerprint
+/// exactly when they do the same thing.
+pub fn fingerprint(alg: &[Move]) -> u128 {
+    Facelets::default().apply_alg(alg).fingerprint()
+}
+
+/// Whether `alg` does nothing at all.
+pub fn is_identity_alg(alg: &[Move]) -> bool {
+    fingerprint(alg) == fingerprint(&[])
+}
+
+/// Whether doing `alg` twice does nothing, like `R2`, or `R U R' U'` done three times. Anything
+/// that does nothing at all counts too.
+pub fn is_self_inverse(alg: &[Move]) -> bool {
+    fingerprint(alg) == fingerprint(&invert(alg))
+}
+
+/// Parses `s` into the groups of turns it is split into by `.`, `,` and line breaks, leaving out
+/// `//` comments up to the end of their line and any empty groups.
+///
+/// # Errors
+///
+/// The same as for parsing a `Move`, for the first token between the separators that isn't one.
+pub fn parse_groups(s: &str) -> Result<Vec<Vec<Move>>, &'static str> {
+    let mut out = vec![];
+    for line in s.lines() {
+        let line = line.split_once("//").map_or(line, |(code, _)| code);
+        for group in line.split(['.', ',']) {
+            let group = group
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<Result<Vec<Move>, _>>()?;
+            if !group.is_empty() {
+                out.push(group);
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Parses `s` with wide turns (`Rw` or `r`), slice turns (`M`, `E` and `S`) and rotations (`x`, `y`
+/// and `z`) allowed alongside the outer turns, writing each of them out as outer turns and
+/// rotations.
+///
+/// # Errors
+///
+/// The same as for parsing a `Move` or an `AxialRotation`, for the first token that is neither.
+pub fn parse_extended(s: &str) -> Result<Vec<MoveOrRot>, &'static str> {
+    let mut out = vec![];
+    for token in s.split_whitespace() {
+        out.extend(expand_token(token)?);
+    }
+    Ok(out)
+}
+
+fn expand_token(token: &str) -> Result<Vec<MoveOrRot>, &'static str> {
+    // the whole cube turned the same way as `face`
+    let rotate = |face: Face, by: Z4| {
+        MoveOrRot::Rot(Rotation::axial(
+            face.axis(),
+            if face.neg() { -by } else { by },
+        ))
+    };
+
+    if let Ok(mv) = token.parse::<Move>() {
+        return Ok(vec![MoveOrRot::Move(mv)]);
+    }
+    if let Ok(rot) = token.parse::<AxialRotation>() {
+        return Ok(vec![MoveOrRot::Rot(rot.into())]);
+    }
+
+    let mut chars = token.chars();
+    let first = chars.next().ok_or("empty move")?;
+    let rest = chars.as_str();
+    // the slice turns, by the face each of them turns like
+    if let Some(face) = match first {
+        'M' => Some(Face::L),
+        'E' => Some(Face::D),
+        'S' => Some(Face::F),
+        _ => None,
+    } {
+        let by = parse_amount(rest)?;
+        return Ok(vec![
+            MoveOrRot::Move(Move::new(face, -by)),
+            MoveOrRot::Move(Move::new(face.opposite(), by)),
+            rotate(face, by),
+        ]);
+    }
+
+    let (face, by) = match rest.strip_prefix('w') {
+        Some(by) => (first.to_string().parse::<Move>()?.face(), by),
+        None if first.is_ascii_lowercase() => (
+            first
+                .to_ascii_uppercase()
+                .to_string()
+                .parse::<Move>()?
+                .face(),
+            rest,
+        ),
+        None => return Err("bad move"),
+    };
+    let by = parse_amount(by)?;
+    Ok(vec![
+        MoveOrRot::Move(Move::new(face.opposite(), by)),
+        rotate(face, by),
+    ])
+}
+
+/// Whether doing `solution` after `scramble` solves the cube, in any orientation.
+pub fn solves(scramble: &[Move], solution: &[MoveOrRot]) -> bool {
+    Facelets::default()
+        .apply_alg(scramble)
+        .apply_solution(solution)
+        .is_solved()
+}
+
+/// `alg` as one byte per turn, from `Move::to_byte`.
+pub fn to_bytes(alg: &[Move]) -> Vec<u8> {
+    alg.iter().map(|mv| mv.to_byte()).collect()
+}
+
+/// The inverse of `to_bytes`. `None` if any byte isn't a turn.
+pub fn from_bytes(bytes: &[u8]) -> Option<Vec<Move>> {
+    bytes.iter().map(|&byte| Move::from_byte(byte)).collect()
+}
+
+/// A random sequence of `len` turns, the same for the same `seed`. No turn is on the same face as
+/// the one before it, and no three turns in a row are on the same axis, so nothing cancels.
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "the values are taken mod 6 first"
+)]
+pub fn random_scramble(len: usize, seed: u64) -> Vec<Move> {
+    // splitmix64
+    let mut state = seed;
+    let mut next = || {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    };
+
+    let mut out: Vec<Move> = Vec::with_capacity(len);
+    while out.len() < len {
+        let face = Face::ALL[(next() % 6) as usize];
+        let by = Z4::ALL[(next() % 3 + 1) as usize];
+        let mv = Move::new(face, by);
+
+        let redundant = match out[..] {
+            [.., a, b] => b.face() == face || (a.commutes(mv) && b.commutes(mv)),
+            [.., b] => b.face() == face,
+            [] => false,
+        };
+        if !redundant {
+            out.push(mv);
+        }
+    }
+    out
+}
+
+/// `alg` in WCA notation, with identity turns left out.
+pub fn format_wca(alg: &[Move]) -> String {
+    alg.iter()
+        .filter(|mv| !mv.is_identity())
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// `recon` in WCA notation, with identity turns left out and rotations written as `x`, `y`, `z`.
+pub fn format_wca_solution(recon: &[MoveOrRot]) -> String {
+    format_wca_solution_with(recon, FormatOptions::default())
+}
+
+/// How `format_wca_solution_with` lays out a solution. The default is a single line with one
+/// space between tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FormatOptions {
+    /// Put two spaces instead of one after every this many turns. Rotations aren't counted.
+    pub group: Option<usize>,
+    /// Start a new line at every run of rotations.
+    pub break_at_rotations: bool,
+}
+
+/// Like `format_wca_solution`, laid out according to `options`.
+pub fn format_wca_solution_with(recon: &[MoveOrRot], options: FormatOptions) -> String {
+    let mut out = String::new();
+    let mut turns = 0;
+    let mut prev = None;
+    for v in expand_rotations(recon) {
+        if matches!(v, MoveOrAxialRot::Move(mv) if mv.is_identity()) {
+            continue;
+        }
+
+        match (prev, v) {
+            (None, _) => {}
+            (Some(MoveOrAxialRot::Move(_)), MoveOrAxialRot::Rot(_))
+                if options.break_at_rotations =>
+            {
+                out.push('\n');
+            }
+            (Some(MoveOrAxialRot::Move(_)), _)
+                if options.group.is_some_and(|n| n != 0 && turns % n == 0) =>
+            {
+                out.push_str("  ");
+            }
+            _ => out.push(' '),
+        }
+
+        if matches!(v, MoveOrAxialRot::Move(_)) {
+            turns += 1;
+        }
+        out.push_str(&v.to_string());
+        prev = Some(v);
+    }
+    out
+}
+
+fn alg_cubing_url_of(notation: &str) -> String {
+    let encoded: String = notation
+        .bytes()
+        .map(|byte| {
+            if byte.is_ascii_alphanumeric() {
+                char::from(byte).to_string()
+            } else {
+                format!("%{byte:02X}")
+            }
+        })
+        .collect();
+    format!("https://alg.cubing.net/?alg={encoded}")
+}
+
+/// A link showing `alg` on alg.cubing.net.
+pub fn alg_cubing_url(alg: &[Move]) -> String {
+    alg_cubing_url_of(&format_wca(alg))
+}
+
+pub fn alg_cubing_url_solution(recon: &[MoveOrRot]) -> String {
+    alg_cubing_url_of(&format_wca_solution(recon))
+}
+
+/// The same representative for every algorithm that is `alg` up to holding the cube differently:
+/// the smallest of its `normalize`d forms in all 24 orientations. Mirrors are not included, so an
+/// algorithm and its `mirror` usually have different canonical forms.
+#[expect(clippy::missing_panics_doc, reason = "`Rotation::ALL` isn't empty")]
+pub fn canonical(alg: &[Move]) -> Vec<Move> {
+    Rotation::ALL
+        .map(|rot| normalize(&rotate_alg(alg, rot)))
+        .into_iter()
+        .min_by_key(|alg| axial_form(alg.iter().copied()))
+        .unwrap()
+}
+
+/// Whether doing `a` then `b` leaves the cube the same as doing `b` then `a`.
+pub fn commute(a: &[Move], b: &[Move]) -> bool {
+    let start = Facelets::default();
+    start.apply_alg(a).apply_alg(b) == start.apply_alg(b).apply_alg(a)
+}
+
+/// `by a by'`.
+pub fn conjugate(a: &[Move], by: &[Move]) -> Vec<Move> {
+    [by, a, &invert(by)].concat()
+}
+
+/// A sequence of turns that parses from and displays as space-separated notation.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Alg(Vec<Move>);
+
+impl Alg {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, mv: Move) {
+        self.0.push(mv);
+    }
+
+    /// Like `push`, but merges `mv` into the last turn if it is on the same face, removing the turn
+    /// if they cancel out.
+    pub fn push_merged(&mut self, mv: Move) {
+        if let Some(last) = self.0.last_mut()
+            && let Some(merged) = last.compose(mv)
+        {
+            *last = merged;
+            if merged.is_identity() {
+                self.0.pop();
+            }
+        } else {
+            self.0.push(mv);
+        }
+    }
+
+    pub fn normalize(&self) -> Self {
+        Self(normalize(self))
+    }
+
+    pub fn into_vec(self) -> Vec<Move> {
+        self.0
+    }
+}
+
+impl Deref for Alg {
+    type Target = [Move];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Vec<Move>> for Alg {
+    fn from(value: Vec<Move>) -> Self {
+        Self(value)
+    }
+}
+
+impl FromIterator<Move> for Alg {
+    fn from_iter<T: IntoIterator<Item = Move>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Display for Alg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, mv) in self.iter().enumerate() {
+            if i != 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{mv}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Alg {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split_whitespace().map(str::parse).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "quickcheck")]
+    use quickcheck::{QuickCheck, Testable};
+
+    use super::*;
+    use crate::facelets::Facelets;
+
+    #[cfg(feature = "quickcheck")]
+    fn check(prop: impl Testable) {
+        QuickCheck::new().tests(500).quickcheck(prop);
+    }
+
+    #[test]
+    fn invert_undoes_algorithms() {
+        let alg = parse_groups("R U2 F' D").unwrap().concat();
+        assert_eq!(format_wca(&invert(&alg)), "D' F U2 R'");
+        assert_eq!(invert(&invert(&alg)), alg);
+
+        for seed in 0..10 {
+            let scramble = random_scramble(8, seed);
+            assert_eq!(invert(&invert(&scramble)), scramble);
+            assert!(is_identity_alg(
+                &[scramble.clone(), invert(&scramble)].concat()
+            ));
+        }
+    }
+
+    #[test]
+    fn invert_solution_keeps_rotations_in_place() {
+        let seq = parse_extended("R x U' y2 F").unwrap();
+        let inverted = invert_solution(&seq);
+        assert_eq!(format_wca_solution(&inverted), "F' y2 U x' R'");
+        assert_eq!(
+            format_wca_solution(&invert_solution(&inverted)),
+            format_wca_solution(&seq)
+        );
+
+        let undone = [seq, inverted].concat();
+        assert_eq!(net_rotation(&undone), Rotation::ID);
+        assert!(Facelets::default().apply_solution(&undone).is_solved());
+    }
+
+    #[test]
+    fn mirror_gives_the_left_hand_version() {
+        for (alg, axis, mirrored) in [
+            ("R U R' U'", Axis::X, "L' U' L U"),
+            ("R U R'", Axis::Y, "R' D' R"),
+            ("F R U2", Axis::Z, "B' R' U2"),
+        ] {
+            let alg = parse_groups(alg).unwrap().concat();
+            assert_eq!(format_wca(&mirror(&alg, axis)), mirrored);
+            assert_eq!(mirror(&mirror(&alg, axis), axis), alg);
+        }
+
+        // a mirror image does as much as the original: undone by the mirror of its inverse, and
+        // only back to solved after as many repetitions
+        let sexy = parse_groups("R U R' U'").unwrap().concat();
+        for axis in Axis::ALL {
+            let mirrored = mirror(&sexy, axis);
+            let undone = [mirrored.clone(), mirror(&invert(&sexy), axis)].concat();
+            assert!(Facelets::default().apply_alg(&undone).is_solved());
+            let mut state = Facelets::default();
+            for i in 1..=6 {
+                state = state.apply_alg(&mirrored);
+                assert_eq!(state.is_solved(), i == 6);
+            }
+        }
+    }
+
+    #[test]
+    fn annotate_ergonomics_follows_the_grip() {
+        let flags = |s| -> Vec<bool> {
+            annotate_ergonomics(&parse_extended(s).unwrap())
+                .into_iter()
+                .map(|(_, ergonomic)| ergonomic)
+                .collect()
+        };
+
+        assert_eq!(flags("R U R' L' F"), [true; 5]);
+        // turning the cube to reach `B` is one regrip, and `B` as seen from there is still awkward
+        assert_eq!(flags("R U R' y B"), [true, true, true, false, false]);
+        assert_eq!(flags("R U R' D"), [true, true, true, false]);
+        // a change of grip made of several rotations is a single regrip
+        assert_eq!(flags("R z y2 U"), [true, false, true, true]);
+        // and none at all if they undo each other
+        assert_eq!(flags("R y y' U"), [true; 4]);
+        assert_eq!(flags("R y U y' U"), [true, false, true, false, true]);
+    }
+
+    #[cfg(feature = "quickcheck")]
+    // strings made of the characters that turn up in algorithms, to reach the parsers' error
+    // paths more often than arbitrary text does
+    fn alg_like(bytes: &[u8]) -> String {
+        const CHARS: &[u8] = b"RUFLDBrufldbMESxyzw0123' .,/\n";
+        bytes
+            .iter()
+            .map(|&b| char::from(CHARS[usize::from(b) % CHARS.len()]))
+            .collect()
+    }
+
+    #[cfg(feature = "quickcheck")]
+    #[test]
+    fn parse_round_trips() {
+        fn prop(alg: Vec<Move>) -> bool {
+            let alg: Vec<Move> = alg.into_iter().filter(|mv| !mv.is_identity()).collect();
+            let s = format_wca(&alg);
+            parse_groups(&s).map(|groups| groups.concat()).as_ref() == Ok(&alg)
+                && s.parse::<Alg>().map(Alg::into_vec).as_ref() == Ok(&alg)
+        }
+        check(prop as fn(Vec<Move>) -> bool);
+    }
+
+    #[cfg(feature = "quickcheck")]
+    #[test]
+    fn parse_extended_round_trips() {
+        fn prop(alg: Vec<Move>, rots: Vec<Rotation>) -> bool {
+            let seq: Vec<MoveOrRot> = alg
+                .into_iter()
+                .filter(|mv| !mv.is_identity())
+                .map(MoveOrRot::Move)
+                .zip(rots.into_iter().map(MoveOrRot::Rot))
+                .flat_map(|(mv, rot)| [mv, rot])
+                .collect();
+            let Ok(parsed) = parse_extended(&format_wca_solution(&seq)) else {
+                return false;
+            };
+            flatten(&parsed) == flatten(&seq) && net_rotation(&parsed) == net_rotation(&seq)
+        }
+        check(prop as fn(Vec<Move>, Vec<Rotation>) -> bool);
+    }
+
+    #[cfg(feature = "quickcheck")]
+    #[test]
+    fn parsers_never_panic() {
+        #[expect(
+            clippy::needless_pass_by_value,
+            reason = "quickcheck hands over owned values"
+        )]
+        fn prop(bytes: Vec<u8>, s: String) -> bool {
+            for s in [alg_like(&bytes), s] {
+                let _ = parse_groups(&s);
+                let _ = parse_extended(&s);
+                let _ = s.parse::<Alg>();
+            }
+            true
+        }
+        check(prop as fn(Vec<u8>, String) -> bool);
+    }
+
+    #[cfg(feature = "quickcheck")]
+    #[test]
+    fn bytes_round_trip() {
+        #[expect(
+            clippy::needless_pass_by_value,
+            reason = "quickcheck hands over owned values"
+        )]
+        fn prop(alg: Vec<Move>, bytes: Vec<u8>) -> bool {
+            from_bytes(&to_bytes(&alg)).as_ref() == Some(&alg)
+                && from_bytes(&bytes).is_none_or(|alg| to_bytes(&alg) == bytes)
+        }
+        check(prop as fn(Vec<Move>, Vec<u8>) -> bool);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{
+    MoveOrRot,
+    data::{Axis, Face, Move, Rotation},
+};
+
+// the corner positions are numbered by which of their faces are negative: bit 0 for the x face,
+// bit 1 for the y face and bit 2 for the z face
+const fn corner_faces(idx: usize) -> [Face; 3] {
+    [
+        Face::new(Axis::X, idx & 1 != 0),
+        Face::new(Axis::Y, idx & 2 != 0),
+        Face::new(Axis::Z, idx & 4 != 0),
+    ]
+}
+
+fn corner_index(faces: [Face; 3]) -> usize {
+    faces
+        .iter()
+        .filter(|face| face.neg())
+        .map(|face| match face.axis() {
+            Axis::X => 1,
+            Axis::Y => 2,
+            Axis::Z => 4,
+        })
+        .sum()
+}
+
+/// The corners of a cube, as a ground truth to check reconstructions against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CubeState {
+    // which corner is in each position
+    perm: [u8; 8],
+    // for each position, the axis of the face the corner's U/D sticker is on
+    ori: [Axis; 8],
+}
+
+impl Default for CubeState {
+    fn default() -> Self {
+        Self {
+            perm: [0, 1, 2, 3, 4, 5, 6, 7],
+            ori: [Axis::Y; 8],
+        }
+    }
+}
+
+impl CubeState {
+    // moves the stickers on each face `f` to `map(f)`, for the corners for which `affected` holds
+    fn map(self, map: impl Fn(Face) -> Face, affected: impl Fn([Face; 3]) -> bool) -> Self {
+        let mut out = self;
+        for pos in 0..8 {
+            let faces = corner_faces(pos);
+            if !affected(faces) {
+                continue;
+            }
+
+            let new = corner_index(faces.map(&map));
+            out.perm[new] = self.perm[pos];
+            out.ori[new] = map(self.ori[pos].pos_face()).axis();
+        }
+        out
+    }
+
+    pub fn apply_move(self, mv: Move) -> Self {
+        self.map(|face| mv.apply(face), |faces| faces.contains(&mv.face()))
+    }
+
+    pub fn apply_rotation(self, rot: Rotation) -> Self {
+        self.map(|face| face * rot, |_| true)
+    }
+
+    pub fn apply_alg(self, alg: &[Move]) -> Self {
+        alg.iter().fold(self, |state, &mv| state.apply_move(mv))
+    }
+
+    pub fn apply_solution(self, recon: &[MoveOrRot]) -> Self {
+        recon.iter().fold(self, |state, &v| match v {
+            MoveOrRot::Move(mv) => state.apply_move(mv),
+            MoveOrRot::Rot(rot) => state.apply_rotation(rot),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::{alg, data::Z4, facelets::Facelets};
+
+    fn moves() -> impl Iterator<Item = Move> {
+        Face::ALL
+            .into_iter()
+            .flat_map(|face| Z4::ALL.map(|by| Move::new(face, by)))
+    }
+
+    #[test]
+    fn doing_nothing_is_the_identity() {
+        let solved = CubeState::default();
+        assert_eq!(solved.apply_alg(&[]), solved);
+        assert_eq!(solved.apply_rotation(Rotation::ID), solved);
+        assert_eq!(solved.apply_solution(&[]), solved);
+    }
+
+    #[test]
+    fn four_quarter_turns_are_the_identity() {
+        for face in Face::ALL {
+            let mv = Move::new(face, Z4::One);
+            assert_ne!(CubeState::default().apply_move(mv), CubeState::default());
+            assert_eq!(
+                CubeState::default().apply_alg(&[mv; 4]),
+                CubeState::default()
+            );
+        }
+    }
+
+    #[test]
+    fn sexy_move_six_times_is_the_identity() {
+        let sexy = alg::parse_groups("R U R' U'").unwrap().concat();
+        let state = (0..6).fold(CubeState::default(), |state, _| state.apply_alg(&sexy));
+        assert_eq!(state, CubeState::default());
+        assert_ne!(CubeState::default().apply_alg(&sexy), CubeState::default());
+    }
+
+    // whenever the facelets can't tell two ways of turning the cube apart, the corners can't either
+    #[test]
+    fn agrees_with_facelets() {
+        let single: Vec<Move> = moves().collect();
+        for rot in Rotation::ALL {
+            for mv in moves() {
+                let conjugated = [
+                    MoveOrRot::Rot(rot),
+                    MoveOrRot::Move(mv),
+                    MoveOrRot::Rot(-rot),
+                ];
+                let facelets = Facelets::default().apply_solution(&conjugated);
+                let same = single
+                    .iter()
+                    .find(|&&other| Facelets::default().apply_move(other) == facelets)
+                    .unwrap();
+                assert_eq!(
+                    CubeState::default().apply_solution(&conjugated),
+                    CubeState::default().apply_move(*same)
+                );
+            }
+        }
+
+        for seed in 0..20 {
+            let scramble = alg::random_scramble(8, seed);
+            let undone = [scramble.clone(), alg::invert(&scramble)].concat();
+            assert!(Facelets::default().apply_alg(&undone).is_solved());
+            assert_eq!(
+                CubeState::default().apply_alg(&undone),
+                CubeState::default()
+            );
+        }
+    }
+}
@@ -1,10 +1,17 @@
 pub mod basic;
+pub mod color;
 mod fmt;
+#[cfg(feature = "quickcheck")]
+mod quickcheck;
 pub mod rotation;
+#[cfg(feature = "serde")]
+mod serde;
 pub mod z4;
 
+pub(crate) use fmt::parse_amount;
 pub use {
     basic::{AxialMove, AxialRotation, Axis, Face, Move},
+    color::{Color, ColorScheme},
     rotation::Rotation,
     z4::Z4,
 };
@@ -1,9 +1,11 @@
 use core::{
+    cmp::Ordering,
     fmt::Debug,
     ops::{Add, Neg, Sub},
 };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Axis {
     X,
     Y,
@@ -33,9 +35,47 @@ impl Axis {
     pub const fn eq(self, rhs: Self) -> bool {
         matches!((self, rhs), (X, X) | (Y, Y) | (Z, Z))
     }
+
+    pub const fn index(self) -> u8 {
+        self as u8
+    }
+
+    /// The axis with `index`, the inverse of `index`.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is 3 or more.
+    pub const fn from_index(index: u8) -> Self {
+        Self::ALL[index as usize]
+    }
+
+    /// The axis at right angles to both `self` and `rhs`, or `None` if they are the same.
+    pub const fn cross(self, rhs: Self) -> Option<Axis> {
+        if self.eq(rhs) {
+            None
+        } else {
+            Some(Self::from_index(3 - self.index() - rhs.index()))
+        }
+    }
+
+    /// The unit vector along `self`, the same as for `pos_face`.
+    pub const fn to_vector(self) -> [i8; 3] {
+        self.pos_face().to_vector()
+    }
+
+    /// The axis along a unit vector pointing either way.
+    pub const fn from_vector(vector: [i8; 3]) -> Option<Axis> {
+        match Face::from_vector(vector) {
+            Some(face) => Some(face.axis()),
+            None => None,
+        }
+    }
+
+    pub const ALL: [Self; 3] = [X, Y, Z];
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Face {
     R,
     U,
@@ -91,9 +131,45 @@ impl Face {
         use Face::*;
         [R, U, F, L, D, B]
     };
+
+    /// The four faces around `self`, in the order a clockwise turn of `self` cycles them: the
+    /// stickers on each go to the next one.
+    pub const fn adjacent(self) -> [Face; 4] {
+        use Face::*;
+        const LUT: [[Face; 4]; 6] = [
+            [U, B, D, F],
+            [R, F, L, B],
+            [R, D, L, U],
+            [U, F, D, B],
+            [R, B, L, F],
+            [R, U, L, D],
+        ];
+        LUT[self as usize]
+    }
+
+    /// The unit vector pointing out of `self`, with `x`, `y` and `z` towards `R`, `U` and `F`. This
+    /// is right-handed: `R` cross `U` is `F`.
+    pub const fn to_vector(self) -> [i8; 3] {
+        let mut out = [0; 3];
+        out[self.axis().index() as usize] = if self.neg() { -1 } else { 1 };
+        out
+    }
+
+    /// The face pointed at by a unit vector, or `None` if `vector` isn't one.
+    pub const fn from_vector(vector: [i8; 3]) -> Option<Face> {
+        match vector {
+            [1, 0, 0] => Some(Face::R),
+            [0, 1, 0] => Some(Face::U),
+            [0, 0, 1] => Some(Face::F),
+            [-1, 0, 0] => Some(Face::L),
+            [0, -1, 0] => Some(Face::D),
+            [0, 0, -1] => Some(Face::B),
+            _ => None,
+        }
+    }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Move {
     face: Face,
     by: Z4,
@@ -116,6 +192,14 @@ impl Move {
         self.face().axis()
     }
 
+    pub const fn none(face: Face) -> Self {
+        Self::new(face, Z4::Zero)
+    }
+
+    pub const fn is_identity(self) -> bool {
+        matches!(self.by(), Z4::Zero)
+    }
+
     pub const fn commutes(self, rhs: Move) -> bool {
         self.face().is_coaxial(rhs.face())
     }
@@ -123,6 +207,54 @@ impl Move {
     pub const fn inv(self) -> Move {
         Self::new(self.face(), self.by().neg())
     }
+
+    /// The smallest `k > 0` with `self` done `k` times doing nothing: 1, 2 or 4.
+    pub const fn order(self) -> u8 {
+        match self.by() {
+            Z4::Zero => 1,
+            Z4::Two => 2,
+            Z4::One | Z4::Three => 4,
+        }
+    }
+
+    /// Where the stickers on `face` go when doing `self`. Only the faces in
+    /// `self.face().adjacent()` move.
+    pub const fn apply(self, face: Face) -> Face {
+        let adjacent = self.face().adjacent();
+        let mut i = 0;
+        while i < adjacent.len() {
+            if adjacent[i] as u8 == face as u8 {
+                return adjacent[(i + self.by().val() as usize) % 4];
+            }
+            i += 1;
+        }
+        face
+    }
+
+    /// `self` packed into a byte below 24, for compact storage.
+    pub const fn to_byte(self) -> u8 {
+        self.face() as u8 * 4 + self.by().val()
+    }
+
+    pub const fn from_byte(byte: u8) -> Option<Move> {
+        if byte < 24 {
+            Some(Self::new(
+                Face::ALL[(byte / 4) as usize],
+                Z4::from_val(byte % 4),
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// The single turn doing `self` then `rhs`, if they are on the same face.
+    pub const fn compose(self, rhs: Move) -> Option<Move> {
+        if self.face() as u8 == rhs.face() as u8 {
+            Some(Self::new(self.face(), self.by().add(rhs.by())))
+        } else {
+            None
+        }
+    }
 }
 
 impl Neg for Move {
@@ -133,7 +265,7 @@ impl Neg for Move {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct AxialMove {
     axis: Axis,
     pos: Z4,
@@ -141,6 +273,7 @@ pub struct AxialMove {
 }
 
 impl AxialMove {
+    /// Note that a zero move is always stored on `X`, whatever `axis` is given.
     pub const fn new(mut axis: Axis, pos: Z4, neg: Z4) -> Self {
         if matches!((pos, neg), (Z4::Zero, Z4::Zero)) {
             axis = X;
@@ -185,10 +318,21 @@ impl AxialMove {
         }
     }
 
+    /// The turns of `moves` that actually do something, the positive face first.
+    pub fn nonzero_moves(self) -> impl Iterator<Item = Move> {
+        let (a, b) = self.moves();
+        [a, b].into_iter().filter(|mv| !mv.is_identity())
+    }
+
     pub const fn inv(self) -> AxialMove {
         Self::new(self.axis(), self.pos().neg(), self.neg().neg())
     }
 
+    /// `self` done `n` times in a row. `repeat(4)` is always `ZERO`.
+    pub const fn repeat(self, n: u8) -> AxialMove {
+        Self::new(self.axis(), self.pos().mul(n), self.neg().mul(n))
+    }
+
     #[expect(clippy::should_implement_trait)]
     pub fn add(self, rhs: AxialMove) -> Option<AxialMove> {
         if self.is_zero() {
@@ -205,6 +349,49 @@ impl AxialMove {
             ))
         }
     }
+
+    /// Like `add`, but only for the same stored axis, zeros included: as zeros are stored on `X`,
+    /// `ZERO` plus `U` is `None`, and `U` plus `U'` comes back on `X`.
+    pub const fn checked_add_same_axis(self, rhs: AxialMove) -> Option<AxialMove> {
+        if self.axis() as u8 == rhs.axis() as u8 {
+            Some(Self::new(
+                self.axis(),
+                self.pos().add(rhs.pos()),
+                self.neg().add(rhs.neg()),
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+// `new` always puts zeros on `X`, so equal values compare equal here too
+impl Ord for AxialMove {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.axis() as u8, self.pos(), self.neg()).cmp(&(
+            other.axis() as u8,
+            other.pos(),
+            other.neg(),
+        ))
+    }
+}
+
+impl PartialOrd for AxialMove {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<(Face, Z4)> for Move {
+    fn from((face, by): (Face, Z4)) -> Self {
+        Self::new(face, by)
+    }
+}
+
+impl From<Move> for (Face, Z4) {
+    fn from(value: Move) -> Self {
+        (value.face(), value.by())
+    }
 }
 
 impl From<Move> for AxialMove {
@@ -243,7 +430,7 @@ impl Sub for AxialMove {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct AxialRotation {
     axis: Axis,
     by: Z4,
@@ -262,3 +449,121 @@ impl AxialRotation {
         self.by
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    fn axial_moves() -> impl Iterator<Item = AxialMove> {
+        Axis::ALL.into_iter().flat_map(|axis| {
+            Z4::ALL
+                .into_iter()
+                .flat_map(move |pos| Z4::ALL.map(|neg| AxialMove::new(axis, pos, neg)))
+        })
+    }
+
+    #[test]
+    fn axial_move_order_agrees_with_eq() {
+        for a in axial_moves() {
+            for b in axial_moves() {
+                assert_eq!(a.cmp(&b) == Ordering::Equal, a == b, "{a:?} {b:?}");
+                assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+            }
+        }
+
+        // the zeros made on each axis are all the same, so they end up next to each other
+        let mut sorted: Vec<AxialMove> = axial_moves().collect();
+        sorted.sort();
+        let zeros: Vec<usize> = (0..sorted.len()).filter(|&i| sorted[i].is_zero()).collect();
+        assert_eq!(zeros, [0, 1, 2]);
+    }
+
+    #[test]
+    fn checked_add_same_axis_sees_zeros_on_x() {
+        let u = AxialMove::from(Move::new(Face::U, Z4::One));
+        let r = AxialMove::from(Move::new(Face::R, Z4::One));
+
+        // `add` lets zeros through on any axis, but a zero is stored on `X`
+        assert_eq!(AxialMove::ZERO.add(u), Some(u));
+        assert_eq!(AxialMove::ZERO.checked_add_same_axis(u), None);
+        assert_eq!(u.checked_add_same_axis(AxialMove::ZERO), None);
+        assert_eq!(AxialMove::ZERO.checked_add_same_axis(r), Some(r));
+
+        // cancelling out also lands on `X`
+        let cancelled = u.checked_add_same_axis(u.inv()).unwrap();
+        assert!(cancelled.is_zero());
+        assert_eq!(cancelled.axis(), X);
+        assert_eq!(cancelled, AxialMove::ZERO);
+
+        assert_eq!(u.checked_add_same_axis(r), None);
+        assert_eq!(u.checked_add_same_axis(u), Some(u.repeat(2)));
+        for a in axial_moves() {
+            for b in axial_moves() {
+                if let Some(sum) = a.checked_add_same_axis(b) {
+                    assert_eq!(a.add(b), Some(sum));
+                }
+            }
+        }
+    }
+
+    fn cross([ax, ay, az]: [i8; 3], [bx, by, bz]: [i8; 3]) -> [i8; 3] {
+        [ay * bz - az * by, az * bx - ax * bz, ax * by - ay * bx]
+    }
+
+    #[test]
+    fn vectors_round_trip() {
+        for face in Face::ALL {
+            assert_eq!(Face::from_vector(face.to_vector()), Some(face));
+        }
+        for axis in Axis::ALL {
+            assert_eq!(Axis::from_vector(axis.to_vector()), Some(axis));
+            assert_eq!(Axis::from_vector(axis.neg_face().to_vector()), Some(axis));
+        }
+        for vector in [[0, 0, 0], [1, 1, 0], [2, 0, 0], [0, -1, 1]] {
+            assert_eq!(Face::from_vector(vector), None);
+            assert_eq!(Axis::from_vector(vector), None);
+        }
+    }
+
+    #[test]
+    fn cross_is_right_handed() {
+        use Face::*;
+        assert_eq!(cross(R.to_vector(), U.to_vector()), F.to_vector());
+        assert_eq!(cross(U.to_vector(), F.to_vector()), R.to_vector());
+        assert_eq!(cross(F.to_vector(), R.to_vector()), U.to_vector());
+        assert_eq!(X.cross(Y), Some(Z));
+        assert_eq!(Y.cross(Z), Some(X));
+        assert_eq!(Z.cross(X), Some(Y));
+
+        for a in Face::ALL {
+            for b in Face::ALL {
+                let product = Face::from_vector(cross(a.to_vector(), b.to_vector()));
+                assert_eq!(product.map(Face::axis), a.axis().cross(b.axis()));
+                if let Some(c) = product {
+                    // cycling a right-handed frame keeps it right-handed
+                    assert_eq!(cross(b.to_vector(), c.to_vector()), a.to_vector());
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn equal_axial_moves_hash_the_same() {
+        use std::hash::{BuildHasher, RandomState};
+
+        let state = RandomState::new();
+        for a in axial_moves() {
+            for b in axial_moves() {
+                if a == b {
+                    assert_eq!(state.hash_one(a), state.hash_one(b), "{a:?}");
+                }
+            }
+        }
+        // the zeros made on `Y` and `Z` are stored on `X`, so they hash as `ZERO` does
+        let zeros = Axis::ALL.map(|axis| state.hash_one(AxialMove::new(axis, Z4::Zero, Z4::Zero)));
+        assert_eq!(zeros, [state.hash_one(AxialMove::ZERO); 3]);
+    }
+}
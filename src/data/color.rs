@@ -0,0 +1,51 @@
+use crate::data::basic::Face;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Color {
+    White,
+    Yellow,
+    Green,
+    Blue,
+    Red,
+    Orange,
+}
+
+/// The colour of each face of a solved cube, indexed by `Face as usize`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColorScheme([Color; 6]);
+
+impl ColorScheme {
+    pub const fn new(colors: [Color; 6]) -> Self {
+        Self(colors)
+    }
+
+    /// The standard scheme, held white on top and green in front.
+    pub const WCA: Self = {
+        use Color::*;
+        Self([Red, White, Green, Orange, Yellow, Blue])
+    };
+
+    /// The WCA scheme with blue and yellow swapped, so that white is opposite blue.
+    pub const JAPANESE: Self = {
+        use Color::*;
+        Self([Red, White, Green, Orange, Blue, Yellow])
+    };
+
+    pub const fn get(self, face: Face) -> Color {
+        self.0[face as usize]
+    }
+}
+
+impl Default for ColorScheme {
+    fn default() -> Self {
+        Self::WCA
+    }
+}
+
+impl Face {
+    pub const fn color(self, scheme: ColorScheme) -> Color {
+        scheme.get(self)
+    }
+}
@@ -3,6 +3,12 @@ use core::{
     str::FromStr,
 };
 
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
 use crate::data::{
     basic::{AxialMove, AxialRotation, Axis, Face, Move},
     rotation::Rotation,
@@ -36,10 +42,49 @@ impl Display for Move {
     }
 }
 
+impl Move {
+    /// `self` with the amount always written as a number, e.g. `R1` or `R3` rather than `R` or
+    /// `R'`, which parses back to the same move.
+    pub fn to_string_explicit(self) -> String {
+        format!("{:?}{}", self.face(), self.by().val())
+    }
+}
+
+// any number of quarter turns, taken mod 4 so that e.g. `R4` is `R0` and `R5` is `R`, with a `'`
+// before or after it for turning the other way: `R2'` and `R'2` are both `R2`
+pub(crate) fn parse_amount(s: &str) -> Result<Z4, &'static str> {
+    let (digits, inverted) = match (s.strip_prefix('\''), s.strip_suffix('\'')) {
+        (Some(rest), _) | (None, Some(rest)) => (rest, true),
+        (None, None) => (s, false),
+    };
+
+    let by = if digits.is_empty() {
+        Z4::One
+    } else if digits.bytes().all(|b| b.is_ascii_digit()) {
+        // only the value mod 4 matters, which keeps long numbers from overflowing
+        Z4::from_val(digits.bytes().fold(0, |acc, b| (acc * 10 + (b - b'0')) % 4))
+    } else {
+        return Err("bad amount");
+    };
+    Ok(if inverted { -by } else { by })
+}
+
+// a single token, as the error for e.g. `"R "` would otherwise be about the amount
+fn check_token(s: &str) -> Result<(), &'static str> {
+    if s.trim().is_empty() {
+        Err("empty move")
+    } else if s.contains(char::is_whitespace) {
+        Err("whitespace in move")
+    } else {
+        Ok(())
+    }
+}
+
 impl FromStr for Move {
     type Err = &'static str;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        check_token(s)?;
         for (face_str, face) in [
             ("R", Face::R),
             ("U", Face::U),
@@ -49,15 +94,7 @@ impl FromStr for Move {
             ("B", Face::B),
         ] {
             if let Some(rest) = s.strip_prefix(face_str) {
-                return Ok(Self::new(
-                    face,
-                    match rest {
-                        "" => Z4::One,
-                        "2" => Z4::Two,
-                        "'" => Z4::Three,
-                        _ => return Err("bad amount"),
-                    },
-                ));
+                return Ok(Self::new(face, parse_amount(rest)?));
             }
         }
 
@@ -71,14 +108,8 @@ impl Display for AxialMove {
         if self.is_zero() {
             write!(f, "(0)")
         } else {
-            let (a, b) = self.moves();
-            if a.by() == Z4::Zero {
-                write!(f, "({b})")
-            } else if b.by() == Z4::Zero {
-                write!(f, "({a})")
-            } else {
-                write!(f, "({a} {b})")
-            }
+            let moves: Vec<_> = self.nonzero_moves().map(|mv| mv.to_string()).collect();
+            write!(f, "({})", moves.join(" "))
         }
     }
 }
@@ -104,6 +135,21 @@ impl Display for AxialRotation {
     }
 }
 
+impl FromStr for AxialRotation {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        check_token(s)?;
+        for (axis_str, axis) in [("x", Axis::X), ("y", Axis::Y), ("z", Axis::Z)] {
+            if let Some(rest) = s.strip_prefix(axis_str) {
+                return Ok(Self::new(axis, parse_amount(rest)?));
+            }
+        }
+
+        Err("bad axis")
+    }
+}
+
 debug_as_display!(Rotation);
 impl Display for Rotation {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -124,3 +170,43 @@ impl Display for Rotation {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_empty_and_padded_moves() {
+        assert_eq!("".parse::<Move>(), Err("empty move"));
+        assert_eq!(" ".parse::<Move>(), Err("empty move"));
+        assert_eq!("R ".parse::<Move>(), Err("whitespace in move"));
+        assert_eq!(" R".parse::<Move>(), Err("whitespace in move"));
+        assert_eq!("R 2".parse::<Move>(), Err("whitespace in move"));
+        assert_eq!("x ".parse::<AxialRotation>(), Err("whitespace in move"));
+        assert_eq!("Q".parse::<Move>(), Err("bad face"));
+        assert_eq!("R\"".parse::<Move>(), Err("bad amount"));
+        assert_eq!("R".parse::<Move>(), Ok(Move::new(Face::R, Z4::One)));
+    }
+
+    #[test]
+    fn amounts_are_taken_mod_4() {
+        let r = |by| Ok(Move::new(Face::R, by));
+        assert_eq!("R4".parse::<Move>(), r(Z4::Zero));
+        assert!("R4".parse::<Move>().unwrap().is_identity());
+        assert_eq!("R5".parse::<Move>(), "R".parse::<Move>());
+        assert_eq!("R5".parse::<Move>(), r(Z4::One));
+        assert_eq!("R7".parse::<Move>(), "R'".parse::<Move>());
+        assert_eq!("R'2".parse::<Move>(), r(Z4::Two));
+        assert_eq!("R2'".parse::<Move>(), r(Z4::Two));
+        assert_eq!("R'5".parse::<Move>(), r(Z4::Three));
+        // far too long for any integer type, but only the value mod 4 is kept
+        assert_eq!(
+            "R100000000000000000000000000001".parse::<Move>(),
+            r(Z4::One)
+        );
+        assert_eq!(
+            "y6".parse::<AxialRotation>(),
+            Ok(AxialRotation::new(Axis::Y, Z4::Two))
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use ::quickcheck::{Arbitrary, Gen};
+
+use crate::data::{
+    basic::{AxialMove, AxialRotation, Axis, Face, Move},
+    rotation::Rotation,
+    z4::Z4,
+};
+
+// picks one of the values in `ALL`, which are exactly the valid ones. `choose` only fails on an
+// empty slice
+macro_rules! arbitrary_from_all {
+    ($ty:ty) => {
+        impl Arbitrary for $ty {
+            fn arbitrary(g: &mut Gen) -> Self {
+                *g.choose(&<$ty>::ALL).unwrap()
+            }
+        }
+    };
+}
+
+arbitrary_from_all!(Axis);
+arbitrary_from_all!(Face);
+arbitrary_from_all!(Z4);
+arbitrary_from_all!(Rotation);
+
+impl Arbitrary for Move {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self::new(Face::arbitrary(g), Z4::arbitrary(g))
+    }
+}
+
+impl Arbitrary for AxialRotation {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self::new(Axis::arbitrary(g), Z4::arbitrary(g))
+    }
+}
+
+// both turns are on the one axis, and zeros end up on `X` through `new` like everywhere else
+impl Arbitrary for AxialMove {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self::new(Axis::arbitrary(g), Z4::arbitrary(g), Z4::arbitrary(g))
+    }
+}
@@ -1,11 +1,20 @@
-use core::ops::{Mul, Neg};
+use core::{
+    cmp::Ordering,
+    ops::{Mul, Neg},
+};
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
 
 use crate::data::{
     basic::{AxialMove, AxialRotation, Axis, Face, Move},
     z4::Z4,
 };
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+// the position of the rotation in `Rotation::ALL`
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Rotation(u8);
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -27,25 +36,50 @@ impl Diagonal {
 
 impl Rotation {
     const fn to_array(self) -> [Diagonal; 4] {
+        let bits = Self::BITS[self.0 as usize];
         let mut res = [Diagonal::UFR; 4];
         let mut i = 0;
         while i < res.len() {
-            res[i] = Diagonal::from_u8(self.0 >> (2 * i));
+            res[i] = Diagonal::from_u8(bits >> (2 * i));
             i += 1;
         }
         res
     }
 
-    const fn from_array(arr: [Diagonal; 4]) -> Self {
+    const fn to_bits(arr: [Diagonal; 4]) -> u8 {
         let mut res = 0;
         let mut i = 0;
         while i < arr.len() {
             res |= (arr[i] as u8) << (2 * i);
             i += 1;
         }
-        Self(res)
+        res
+    }
+
+    const fn from_array(arr: [Diagonal; 4]) -> Self {
+        let bits = Self::to_bits(arr);
+        let mut i = 0;
+        while Self::BITS[i as usize] != bits {
+            i += 1;
+        }
+        Self(i)
     }
 
+    // the packed diagonals of each rotation, in the order of `ALL`
+    const BITS: [u8; 24] = {
+        let mut result = [0; 24];
+        let mut idx = 0;
+        while idx < 24 {
+            let j = idx / 6;
+            let i = idx % 6;
+            let mut arr = Self::DATA[i];
+            arr.rotate_right(j);
+            result[idx] = Self::to_bits(arr);
+            idx += 1;
+        }
+        result
+    };
+
     const DATA: [[Diagonal; 4]; 6] = {
         use Diagonal::*;
         [
@@ -86,14 +120,13 @@ impl Rotation {
     }
 
     pub const fn get(self, face: Face) -> Face {
-        const LUT: [[Face; 6]; 256] = {
-            let mut out = [[Face::R; 6]; 256];
+        const LUT: [[Face; 6]; 24] = {
+            let mut out = [[Face::R; 6]; 24];
             let mut i = 0;
             while i < Rotation::ALL.len() {
                 let mut j = 0;
                 while j < Face::ALL.len() {
-                    let rot = Rotation::ALL[i];
-                    out[rot.0 as usize][j] = rot.get_(Face::ALL[j]);
+                    out[i][j] = Rotation::ALL[i].get_(Face::ALL[j]);
                     j += 1;
                 }
                 i += 1;
@@ -101,7 +134,7 @@ impl Rotation {
             out
         };
 
-        LUT[self.0 as usize][face as usize]
+        LUT[self.index() as usize][face as usize]
     }
 
     const fn apply_(self, face: Face) -> Face {
@@ -109,14 +142,13 @@ impl Rotation {
     }
 
     pub const fn apply(self, face: Face) -> Face {
-        const LUT: [[Face; 6]; 256] = {
-            let mut out = [[Face::R; 6]; 256];
+        const LUT: [[Face; 6]; 24] = {
+            let mut out = [[Face::R; 6]; 24];
             let mut i = 0;
             while i < Rotation::ALL.len() {
                 let mut j = 0;
                 while j < Face::ALL.len() {
-                    let rot = Rotation::ALL[i];
-                    out[rot.0 as usize][j] = rot.apply_(Face::ALL[j]);
+                    out[i][j] = Rotation::ALL[i].apply_(Face::ALL[j]);
                     j += 1;
                 }
                 i += 1;
@@ -124,7 +156,7 @@ impl Rotation {
             out
         };
 
-        LUT[self.0 as usize][face as usize]
+        LUT[self.index() as usize][face as usize]
     }
 
     const fn inv_(self) -> Self {
@@ -139,18 +171,17 @@ impl Rotation {
     }
 
     pub const fn inv(self) -> Self {
-        const LUT: [Rotation; 256] = {
-            let mut out = [Rotation::ID; 256];
+        const LUT: [Rotation; 24] = {
+            let mut out = [Rotation::ID; 24];
             let mut i = 0;
             while i < Rotation::ALL.len() {
-                let rot = Rotation::ALL[i];
-                out[rot.0 as usize] = rot.inv_();
+                out[i] = Rotation::ALL[i].inv_();
                 i += 1;
             }
             out
         };
 
-        LUT[self.0 as usize]
+        LUT[self.index() as usize]
     }
 
     pub const fn mul_(self, rhs: Self) -> Self {
@@ -185,34 +216,23 @@ impl Rotation {
         LUT[self.index() as usize][rhs.index() as usize]
     }
 
-    pub const ID: Self = Self(0b_11_10_01_00);
+    pub const ID: Self = {
+        use Diagonal::*;
+        Self::from_array([UFR, UFL, DFR, UBR])
+    };
 
     pub const ALL: [Rotation; 24] = {
-        let mut result = [Rotation::ID; 24];
-        let mut idx = 0;
-        while idx < 24 {
-            let j = idx / 6;
-            let i = idx % 6;
-            let mut arr = Self::DATA[i];
-            arr.rotate_right(j);
-            result[idx] = Self::from_array(arr);
-            idx += 1;
+        let mut result = [Rotation(0); 24];
+        let mut i = 0;
+        while i as (usize) < result.len() {
+            result[i as usize] = Self(i);
+            i += 1;
         }
         result
     };
 
     pub const fn index(self) -> u8 {
-        const MAP: [u8; 256] = {
-            let mut out = [0; 256];
-            let mut i = 0;
-            while i as (usize) < Rotation::ALL.len() {
-                out[Rotation::ALL[i as usize].0 as usize] = i;
-                i += 1;
-            }
-            out
-        };
-
-        MAP[self.0 as usize]
+        self.0
     }
 
     pub fn axial(axis: Axis, by: Z4) -> Self {
@@ -224,6 +244,76 @@ impl Rotation {
         Self::from_array(out)
     }
 
+    /// The axial rotations making up `self` in notation, e.g. `x y`. Empty for `ID`.
+    pub fn name(self) -> String {
+        self.to_axials()
+            .map(|rot| rot.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    const fn order_(self) -> u8 {
+        let mut k = 1;
+        let mut acc = self;
+        while acc.index() != Self::ID.index() {
+            acc = acc.mul(self);
+            k += 1;
+        }
+        k
+    }
+
+    const fn fixes_face_(self) -> bool {
+        let mut i = 0;
+        while i < Face::ALL.len() {
+            if self.get(Face::ALL[i]) as u8 == Face::ALL[i] as u8 {
+                return true;
+            }
+            i += 1;
+        }
+        false
+    }
+
+    // the order of each rotation, and whether it keeps some face in place
+    const CLASSES: [(u8, bool); 24] = {
+        let mut out = [(1, true); 24];
+        let mut i = 0;
+        while i < Rotation::ALL.len() {
+            let rot = Rotation::ALL[i];
+            out[i] = (rot.order_(), rot.fixes_face_());
+            i += 1;
+        }
+        out
+    };
+
+    /// The smallest `k > 0` with `self` done `k` times being `ID`.
+    pub const fn order(self) -> u8 {
+        Self::CLASSES[self.index() as usize].0
+    }
+
+    pub const fn is_identity(self) -> bool {
+        self.index() == Self::ID.index()
+    }
+
+    /// A quarter or half turn around a single axis, like `x` or `y2`.
+    pub const fn is_face_rotation(self) -> bool {
+        Self::CLASSES[self.index() as usize].1 && !self.is_identity()
+    }
+
+    /// Whether `self` is its own inverse without being `ID`, like `y2` or `x y2`.
+    pub const fn is_180(self) -> bool {
+        self.order() == 2
+    }
+
+    /// A turn around one of the corner-to-corner diagonals, like `x y`.
+    pub const fn is_120(self) -> bool {
+        self.order() == 3
+    }
+
+    pub fn from_axials(rots: &[AxialRotation]) -> Self {
+        rots.iter()
+            .fold(Self::ID, |acc, &rot| acc * Self::from(rot))
+    }
+
     #[expect(clippy::missing_panics_doc, reason = "the `unwrap` never fails")]
     pub fn to_axials(self) -> impl Iterator<Item = AxialRotation> {
         let r_id = Face::R * self == Face::R;
@@ -271,6 +361,25 @@ impl Rotation {
     }
 }
 
+impl From<AxialRotation> for Rotation {
+    fn from(value: AxialRotation) -> Self {
+        Self::axial(value.axis(), value.by())
+    }
+}
+
+// by position in `ALL`, which has no geometric meaning
+impl Ord for Rotation {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.index().cmp(&other.index())
+    }
+}
+
+impl PartialOrd for Rotation {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Neg for Rotation {
     type Output = Self;
 
@@ -311,6 +420,14 @@ impl Mul<Rotation> for Axis {
     }
 }
 
+impl AxialMove {
+    /// `self * rot`: the same as rotating both of its turns and putting them back together, with
+    /// `pos` and `neg` swapping when the positive face ends up negative.
+    pub fn rotate(self, rot: Rotation) -> AxialMove {
+        self * rot
+    }
+}
+
 impl Mul<Rotation> for AxialMove {
     type Output = Self;
 
@@ -323,3 +440,30 @@ impl Mul<Rotation> for AxialMove {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the same as `AxialMove * Rotation`, one turn at a time
+    fn rotate_each_move(mv: AxialMove, rot: Rotation) -> AxialMove {
+        let (a, b) = mv.moves();
+        AxialMove::from_moves(a * rot, b * rot).unwrap()
+    }
+
+    #[test]
+    fn rotate_agrees_with_rotating_each_move() {
+        for rot in Rotation::ALL {
+            for axis in Axis::ALL {
+                for pos in Z4::ALL {
+                    for neg in Z4::ALL {
+                        let mv = AxialMove::new(axis, pos, neg);
+                        assert_eq!(mv.rotate(rot), rotate_each_move(mv, rot), "{mv:?} {rot:?}");
+                        assert_eq!(mv.rotate(rot), mv * rot);
+                        assert_eq!(mv.rotate(rot).rotate(-rot), mv);
+                    }
+                }
+            }
+        }
+    }
+}
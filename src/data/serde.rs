@@ -0,0 +1,60 @@
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+use alloc::string::String;
+
+use crate::data::{
+    basic::{AxialMove, AxialRotation, Axis, Move},
+    rotation::Rotation,
+    z4::Z4,
+};
+
+// serialized as their notation, so that e.g. JSON stays readable
+macro_rules! serde_as_str {
+    ($ty:ty) => {
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                String::deserialize(deserializer)?
+                    .parse()
+                    .map_err(de::Error::custom)
+            }
+        }
+    };
+}
+
+serde_as_str!(Move);
+serde_as_str!(AxialRotation);
+
+impl Serialize for AxialMove {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.axis(), self.pos(), self.neg()).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for AxialMove {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (axis, pos, neg) = <(Axis, Z4, Z4)>::deserialize(deserializer)?;
+        Ok(Self::new(axis, pos, neg))
+    }
+}
+
+impl Serialize for Rotation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.index().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Rotation {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let index = u8::deserialize(deserializer)?;
+        Rotation::ALL
+            .get(usize::from(index))
+            .copied()
+            .ok_or_else(|| de::Error::custom("bad rotation index"))
+    }
+}
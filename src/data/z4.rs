@@ -1,11 +1,12 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Z4 {
     Zero,
     One,
     Two,
     Three,
 }
-use core::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
 use Z4::*;
 
@@ -37,6 +38,33 @@ impl Z4 {
     pub const fn sub(self, rhs: Self) -> Self {
         Self::from_val(self.val().wrapping_sub(rhs.val()))
     }
+
+    pub const fn mul(self, rhs: u8) -> Self {
+        Self::from_val(self.val().wrapping_mul(rhs))
+    }
+
+    /// `Zero` or `Two`, i.e. no quarter turn.
+    pub const fn is_even(self) -> bool {
+        self.val() & 1 == 0
+    }
+}
+
+impl TryFrom<u8> for Z4 {
+    type Error = &'static str;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        if value < 4 {
+            Ok(Self::from_val(value))
+        } else {
+            Err("out of range")
+        }
+    }
+}
+
+impl From<Z4> for u8 {
+    fn from(value: Z4) -> Self {
+        value.val()
+    }
 }
 
 impl Add for Z4 {
@@ -63,6 +91,14 @@ impl Sub for Z4 {
     }
 }
 
+impl Mul<u8> for Z4 {
+    type Output = Self;
+
+    fn mul(self, rhs: u8) -> Self::Output {
+        self.mul(rhs)
+    }
+}
+
 impl AddAssign for Z4 {
     fn add_assign(&mut self, rhs: Self) {
         *self = *self + rhs;
@@ -74,3 +110,61 @@ impl SubAssign for Z4 {
         *self = *self - rhs;
     }
 }
+
+impl MulAssign<u8> for Z4 {
+    fn mul_assign(&mut self, rhs: u8) {
+        *self = *self * rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_to_and_from_u8() {
+        for z in Z4::ALL {
+            assert_eq!(Z4::try_from(u8::from(z)), Ok(z));
+        }
+        for val in 4..=u8::MAX {
+            assert_eq!(Z4::try_from(val), Err("out of range"));
+        }
+        // `from_val` keeps wrapping around instead
+        assert_eq!(Z4::from_val(6), Two);
+    }
+
+    #[test]
+    #[expect(clippy::erasing_op, reason = "pinning down that `z * 0` is `Zero`")]
+    fn mul_is_repeated_addition() {
+        for z in Z4::ALL {
+            assert_eq!(z * 0, Zero);
+            assert_eq!(z * 4, Zero);
+            assert_eq!(z * 1, z);
+            let mut sum = Zero;
+            for n in 0..=u8::MAX {
+                assert_eq!(z * n, sum, "{z:?} * {n}");
+                let mut assigned = z;
+                assigned *= n;
+                assert_eq!(assigned, z.mul(n));
+                sum += z;
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn equal_values_hash_the_same() {
+        use std::hash::{BuildHasher, RandomState};
+
+        let state = RandomState::new();
+        // the same value, reached in different ways
+        for a in Z4::ALL {
+            for b in Z4::ALL {
+                let sum = Z4::from_val(a.val() + b.val());
+                assert_eq!(a + b, sum);
+                assert_eq!(state.hash_one(a + b), state.hash_one(sum));
+                assert_eq!(state.hash_one(a + b), state.hash_one(b + a));
+            }
+        }
+    }
+}
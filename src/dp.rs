@@ -3,6 +3,8 @@ use core::{
     ops::{Index, IndexMut},
 };
 
+use alloc::vec::Vec;
+
 use crate::data::{AxialMove, Axis, Face, Rotation, Z4};
 
 pub trait DpIndex {
@@ -10,18 +12,18 @@ pub trait DpIndex {
 
     fn size(v: &Self::Runtime) -> usize;
 
-    fn to_index(&self, v: &Self::Runtime) -> usize;
-}
+    // `None` if the size doesn't fit in a `usize`, where `size` would overflow
+    fn checked_size(v: &Self::Runtime) -> Option<usize> {
+        Some(Self::size(v))
+    }
 
-impl<T: DpIndex> DpIndex for &T {
-    type Runtime = T::Runtime;
+    fn to_index(&self, v: &Self::Runtime) -> usize;
 
-    fn size(v: &Self::Runtime) -> usize {
-        T::size(v)
-    }
+    fn from_index(index: usize, v: &Self::Runtime) -> Self;
 
-    fn to_index(&self, v: &Self::Runtime) -> usize {
-        (*self).to_index(v)
+    // `None` if `self` is out of range for `v`, which `to_index` only checks in debug builds
+    fn checked_to_index(&self, v: &Self::Runtime) -> Option<usize> {
+        Some(self.to_index(v))
     }
 }
 
@@ -42,6 +44,13 @@ macro_rules! dp_tuple {
                 size
             }
 
+            fn checked_size(v: &Self::Runtime) -> Option<usize> {
+                let ($($a,)*) = v;
+                let size = 1;
+                $(let size = usize::checked_mul(size, <$T as DpIndex>::checked_size($a)?)?;)*
+                Some(size)
+            }
+
             fn to_index(&self, v: &Self::Runtime) -> usize {
                 let ($($a,)*) = self;
                 let ($($b,)*) = v;
@@ -51,6 +60,28 @@ macro_rules! dp_tuple {
                 index += <$T as DpIndex>::to_index($a, $b);)*
                 index
             }
+
+            fn checked_to_index(&self, v: &Self::Runtime) -> Option<usize> {
+                let ($($a,)*) = self;
+                let ($($b,)*) = v;
+                let mut index = 0;
+                index += 0;
+                $(index *= <$T as DpIndex>::size($b);
+                index += <$T as DpIndex>::checked_to_index($a, $b)?;)*
+                Some(index)
+            }
+
+            #[allow(clippy::unused_unit, reason = "the empty tuple")]
+            fn from_index(index: usize, v: &Self::Runtime) -> Self {
+                let ($($b,)*) = v;
+                let mut stride = Self::size(v);
+                stride *= 1;
+                let _ = (index, stride);
+                ($({
+                    stride /= <$T as DpIndex>::size($b);
+                    <$T as DpIndex>::from_index(index / stride % <$T as DpIndex>::size($b), $b)
+                },)*)
+            }
         }
 
         dp_tuple!(@shrink $($T $a $b)*);
@@ -59,8 +90,43 @@ macro_rules! dp_tuple {
 
 dp_tuple!(A a1 a2 B b1 b2 C c1 c2 D d1 d2 E e1 e2 F f1 f2 G g1 g2 H h1 h2);
 
+impl<I: DpIndex, const N: usize> DpIndex for [I; N] {
+    type Runtime = [I::Runtime; N];
+
+    fn size(v: &Self::Runtime) -> usize {
+        v.iter().map(I::size).product()
+    }
+
+    fn checked_size(v: &Self::Runtime) -> Option<usize> {
+        v.iter()
+            .try_fold(1, |size: usize, v| size.checked_mul(I::checked_size(v)?))
+    }
+
+    fn to_index(&self, v: &Self::Runtime) -> usize {
+        self.iter()
+            .zip(v)
+            .fold(0, |index, (i, v)| index * I::size(v) + i.to_index(v))
+    }
+
+    fn checked_to_index(&self, v: &Self::Runtime) -> Option<usize> {
+        self.iter().zip(v).try_fold(0, |index, (i, v)| {
+            Some(index * I::size(v) + i.checked_to_index(v)?)
+        })
+    }
+
+    fn from_index(mut index: usize, v: &Self::Runtime) -> Self {
+        let mut stride = Self::size(v);
+        core::array::from_fn(|j| {
+            stride /= I::size(&v[j]);
+            let i = I::from_index(index / stride, &v[j]);
+            index %= stride;
+            i
+        })
+    }
+}
+
 macro_rules! dp_as {
-    (|$self:ident: $ty:ty| -> $as:ty $block:block) => {
+    (|$self:ident: $ty:ty| -> $as:ty $block:block, |$val:pat_param| $from:block) => {
         impl DpIndex for $ty {
             type Runtime = ();
 
@@ -72,6 +138,16 @@ macro_rules! dp_as {
                 let val = $block;
                 <$as as DpIndex>::to_index(&val, &Default::default())
             }
+
+            fn checked_to_index(&$self, &(): &()) -> Option<usize> {
+                let val = $block;
+                <$as as DpIndex>::checked_to_index(&val, &Default::default())
+            }
+
+            fn from_index(index: usize, &(): &()) -> Self {
+                let $val = <$as as DpIndex>::from_index(index, &Default::default());
+                $from
+            }
         }
     };
 }
@@ -84,7 +160,11 @@ impl DpIndex for Axis {
     }
 
     fn to_index(&self, &(): &Self::Runtime) -> usize {
-        *self as usize
+        self.index() as usize
+    }
+
+    fn from_index(index: usize, &(): &Self::Runtime) -> Self {
+        Axis::ALL[index]
     }
 }
 
@@ -98,6 +178,10 @@ impl DpIndex for Z4 {
     fn to_index(&self, &(): &Self::Runtime) -> usize {
         self.val() as usize
     }
+
+    fn from_index(index: usize, &(): &Self::Runtime) -> Self {
+        Z4::ALL[index]
+    }
 }
 
 impl DpIndex for bool {
@@ -110,6 +194,10 @@ impl DpIndex for bool {
     fn to_index(&self, &(): &Self::Runtime) -> usize {
         usize::from(*self)
     }
+
+    fn from_index(index: usize, &(): &Self::Runtime) -> Self {
+        index != 0
+    }
 }
 
 impl DpIndex for usize {
@@ -123,10 +211,26 @@ impl DpIndex for usize {
         debug_assert!(self < v);
         *self
     }
+
+    fn checked_to_index(&self, v: &Self::Runtime) -> Option<usize> {
+        (self < v).then_some(*self)
+    }
+
+    fn from_index(index: usize, v: &Self::Runtime) -> Self {
+        debug_assert!(index < *v);
+        index
+    }
 }
 
-dp_as!(|self: Face| -> (Axis, bool) { (self.axis(), self.neg()) });
-dp_as!(|self: AxialMove| -> (Axis, Z4, Z4) { (self.axis(), self.pos(), self.neg()) });
+dp_as!(
+    |self: Face| -> (Axis, bool) { (self.axis(), self.neg()) },
+    |(axis, neg)| { Face::new(axis, neg) }
+);
+// all the zero moves come back as `ZERO`, whichever axis they were stored under
+dp_as!(
+    |self: AxialMove| -> (Axis, Z4, Z4) { (self.axis(), self.pos(), self.neg()) },
+    |(axis, pos, neg)| { AxialMove::new(axis, pos, neg) }
+);
 
 impl DpIndex for Rotation {
     type Runtime = ();
@@ -138,6 +242,10 @@ impl DpIndex for Rotation {
     fn to_index(&self, &(): &Self::Runtime) -> usize {
         self.index() as usize
     }
+
+    fn from_index(index: usize, &(): &Self::Runtime) -> Self {
+        Rotation::ALL[index]
+    }
 }
 
 pub struct DpArray<T, I: DpIndex> {
@@ -147,12 +255,16 @@ pub struct DpArray<T, I: DpIndex> {
 }
 
 impl<T, I: DpIndex> DpArray<T, I> {
+    /// # Panics
+    ///
+    /// If the size of `v` doesn't fit in a `usize`.
     pub fn new_with(v: I::Runtime, f: impl FnMut() -> T) -> Self
     where
         T: Default,
     {
+        let size = I::checked_size(&v).expect("DP array too large");
         Self {
-            inner: core::iter::repeat_with(f).take(I::size(&v)).collect(),
+            inner: core::iter::repeat_with(f).take(size).collect(),
             v,
             _phantom: PhantomData,
         }
@@ -165,6 +277,30 @@ impl<T, I: DpIndex> DpArray<T, I> {
         Self::new_with(v, Default::default)
     }
 
+    /// Makes `self` the same as `new(v)`, reallocating only if it needs more room than it has.
+    ///
+    /// # Panics
+    ///
+    /// The same as `new`.
+    pub fn reset(&mut self, v: I::Runtime)
+    where
+        T: Default,
+    {
+        let size = I::checked_size(&v).expect("DP array too large");
+        self.inner.clear();
+        self.inner.resize_with(size, T::default);
+        self.v = v;
+    }
+
+    /// The number of cells, the same as `I::size` of the shape it was made with.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
     pub fn get(&self, i: &I) -> &T {
         &self.inner[i.to_index(&self.v)]
     }
@@ -172,6 +308,33 @@ impl<T, I: DpIndex> DpArray<T, I> {
     pub fn get_mut(&mut self, i: &I) -> &mut T {
         &mut self.inner[i.to_index(&self.v)]
     }
+
+    pub fn try_get(&self, i: &I) -> Option<&T> {
+        self.inner.get(i.checked_to_index(&self.v)?)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (I, &T)> {
+        let v = &self.v;
+        self.inner
+            .iter()
+            .enumerate()
+            .map(move |(index, t)| (I::from_index(index, v), t))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (I, &mut T)> {
+        let v = &self.v;
+        self.inner
+            .iter_mut()
+            .enumerate()
+            .map(move |(index, t)| (I::from_index(index, v), t))
+    }
+
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.inner.fill(value);
+    }
 }
 
 impl<T, I: DpIndex> Index<I> for DpArray<T, I> {
@@ -201,3 +364,44 @@ impl<T, I: DpIndex> IndexMut<&I> for DpArray<T, I> {
         self.get_mut(index)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_size_catches_overflow() {
+        type Shape = (usize, Rotation, usize);
+        assert_eq!(
+            Shape::checked_size(&(3, (), 5)),
+            Some(Shape::size(&(3, (), 5)))
+        );
+        assert_eq!(Shape::checked_size(&(usize::MAX / 2, (), 2)), None);
+        assert_eq!(<[usize; 2]>::checked_size(&[usize::MAX, 2]), None);
+        assert_eq!(<[usize; 2]>::checked_size(&[4, 2]), Some(8));
+    }
+
+    #[test]
+    fn indexes_z4_arrays_row_major() {
+        let v = [(); 3];
+        assert_eq!(<[Z4; 3]>::size(&v), 64);
+        for i in 0..64 {
+            assert_eq!(<[Z4; 3]>::from_index(i, &v).to_index(&v), i);
+        }
+        // the first entry varies the slowest, as for tuples
+        assert_eq!([Z4::One, Z4::Zero, Z4::Three].to_index(&v), 16 + 3);
+        assert_eq!(
+            [Z4::One, Z4::Zero, Z4::Three].to_index(&v),
+            (Z4::One, Z4::Zero, Z4::Three).to_index(&((), (), ()))
+        );
+
+        let mut array = DpArray::<u32, [Z4; 3]>::new(v);
+        array[[Z4::Two, Z4::One, Z4::Zero]] = 7;
+        assert_eq!(array.iter().filter(|&(_, &x)| x == 7).count(), 1);
+        assert_eq!(array[[Z4::Two, Z4::One, Z4::Zero]], 7);
+        assert_eq!(
+            array.iter().find(|&(_, &x)| x == 7).map(|(i, _)| i),
+            Some([Z4::Two, Z4::One, Z4::Zero])
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use core::fmt::{self, Display};
+
+use crate::{
+    MoveOrRot,
+    data::{Face, Move, Rotation},
+};
+
+// the faces towards the right and the bottom of each face, as laid out in the unfolded net
+const fn layout(face: Face) -> (Face, Face) {
+    use Face::*;
+    match face {
+        R => (B, D),
+        U => (R, F),
+        F => (R, D),
+        L => (F, D),
+        D => (R, B),
+        B => (L, D),
+    }
+}
+
+const fn coord(towards: Face, other: Option<Face>) -> usize {
+    match other {
+        Some(other) if other.is_coaxial(towards) => {
+            if other.neg() == towards.neg() {
+                2
+            } else {
+                0
+            }
+        }
+        _ => 1,
+    }
+}
+
+// a sticker is given by the face it is on, and the other faces of the piece it is on, in the
+// direction of its row and its column
+type Sticker = (Face, Option<Face>, Option<Face>);
+
+fn sticker(idx: usize) -> Sticker {
+    let face = Face::ALL[idx / 9];
+    let (right, down) = layout(face);
+    let (row, col) = (idx / 3 % 3, idx % 3);
+    let along = |towards: Face, i| match i {
+        0 => Some(towards.opposite()),
+        1 => None,
+        _ => Some(towards),
+    };
+    (face, along(down, row), along(right, col))
+}
+
+fn sticker_index((face, a, b): Sticker) -> usize {
+    let (right, down) = layout(face);
+    let find = |towards: Face| {
+        [a, b]
+            .into_iter()
+            .map(|other| coord(towards, other))
+            .find(|&i| i != 1)
+            .unwrap_or(1)
+    };
+    face as usize * 9 + find(down) * 3 + find(right)
+}
+
+/// All 54 stickers of a cube, each holding the face it started on as its colour. Displays as an
+/// unfolded net.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Facelets {
+    stickers: [Face; 54],
+}
+
+impl Default for Facelets {
+    fn default() -> Self {
+        Self {
+            stickers: core::array::from_fn(|idx| Face::ALL[idx / 9]),
+        }
+    }
+}
+
+impl Facelets {
+    /// The colour of the sticker in `row` and `col` of `face`, as drawn in the net.
+    ///
+    /// # Panics
+    ///
+    /// If `row` or `col` is 3 or more.
+    pub fn get(&self, face: Face, row: usize, col: usize) -> Face {
+        assert!(row < 3 && col < 3, "no sticker at row {row}, column {col}");
+        self.stickers[face as usize * 9 + row * 3 + col]
+    }
+
+    // moves the stickers on each face `f` to `map(f)`, for the pieces for which `affected` holds
+    fn map(self, map: impl Fn(Face) -> Face, affected: impl Fn(Sticker) -> bool) -> Self {
+        let mut out = self;
+        for idx in 0..54 {
+            let st @ (face, a, b) = sticker(idx);
+            if !affected(st) {
+                continue;
+            }
+
+            let new = sticker_index((map(face), a.map(&map), b.map(&map)));
+            out.stickers[new] = self.stickers[idx];
+        }
+        out
+    }
+
+    /// The stickers packed into a number, in base 6. The centres are left out, as only rotations
+    /// move them, which leaves 48 digits: just under 125 bits.
+    pub fn fingerprint(&self) -> u128 {
+        self.stickers
+            .iter()
+            .enumerate()
+            .filter(|&(idx, _)| idx % 9 != 4)
+            .fold(0, |acc, (_, &face)| acc * 6 + u128::from(face as u8))
+    }
+
+    /// Whether every face is a single colour, in whatever orientation.
+    pub fn is_solved(&self) -> bool {
+        self.stickers
+            .chunks(9)
+            .all(|face| face.iter().all(|&sticker| sticker == face[0]))
+    }
+
+    pub fn apply_move(self, mv: Move) -> Self {
+        self.map(
+            |face| mv.apply(face),
+            |(face, a, b)| [Some(face), a, b].contains(&Some(mv.face())),
+        )
+    }
+
+    pub fn apply_rotation(self, rot: Rotation) -> Self {
+        self.map(|face| face * rot, |_| true)
+    }
+
+    pub fn apply_alg(self, alg: &[Move]) -> Self {
+        alg.iter().fold(self, |state, &mv| state.apply_move(mv))
+    }
+
+    pub fn apply_solution(self, recon: &[MoveOrRot]) -> Self {
+        recon.iter().fold(self, |state, &v| match v {
+            MoveOrRot::Move(mv) => state.apply_move(mv),
+            MoveOrRot::Rot(rot) => state.apply_rotation(rot),
+        })
+    }
+}
+
+impl Display for Facelets {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let row = |f: &mut fmt::Formatter<'_>, face, row| {
+            (0..3).try_for_each(|col| write!(f, "{:?}", self.get(face, row, col)))
+        };
+
+        for r in 0..3 {
+            write!(f, "    ")?;
+            row(f, Face::U, r)?;
+            writeln!(f)?;
+        }
+
+        for r in 0..3 {
+            row(f, Face::L, r)?;
+            for face in [Face::F, Face::R, Face::B] {
+                write!(f, " ")?;
+                row(f, face, r)?;
+            }
+            writeln!(f)?;
+        }
+
+        for r in 0..3 {
+            write!(f, "    ")?;
+            row(f, Face::D, r)?;
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{alg, cube::CubeState, data::Z4};
+
+    #[test]
+    #[should_panic = "no sticker at row 0, column 5"]
+    fn get_rejects_stickers_off_the_face() {
+        Facelets::default().get(Face::R, 0, 5);
+    }
+
+    #[test]
+    fn doing_nothing_is_the_identity() {
+        let solved = Facelets::default();
+        assert!(solved.is_solved());
+        assert_eq!(solved.apply_alg(&[]), solved);
+        assert_eq!(solved.apply_rotation(Rotation::ID), solved);
+        assert!(
+            Rotation::ALL
+                .iter()
+                .all(|&rot| solved.apply_rotation(rot).is_solved())
+        );
+    }
+
+    #[test]
+    fn four_quarter_turns_are_the_identity() {
+        for face in Face::ALL {
+            let mv = Move::new(face, Z4::One);
+            assert!(!Facelets::default().apply_move(mv).is_solved());
+            assert_eq!(Facelets::default().apply_alg(&[mv; 4]), Facelets::default());
+        }
+    }
+
+    #[test]
+    fn sexy_move_six_times_is_the_identity() {
+        let sexy = alg::parse_groups("R U R' U'").unwrap().concat();
+        let after = |n| (0..n).fold(Facelets::default(), |state, _| state.apply_alg(&sexy));
+        assert_eq!(after(6), Facelets::default());
+        assert!((1..6).all(|n| !after(n).is_solved()));
+    }
+
+    // the corners of the facelets move just like `CubeState` says, so two scrambles leave the
+    // same corners behind exactly when `CubeState` says so
+    #[test]
+    fn agrees_with_cube_state() {
+        for seed in 0..20 {
+            let a = alg::random_scramble(6, seed);
+            let b = alg::random_scramble(6, seed + 20);
+            let corners = |alg: &[Move]| {
+                let facelets = Facelets::default().apply_alg(alg);
+                Face::ALL
+                    .map(|face| [0, 2].map(|row| [0, 2].map(|col| facelets.get(face, row, col))))
+            };
+            assert_eq!(
+                corners(&a) == corners(&b),
+                CubeState::default().apply_alg(&a) == CubeState::default().apply_alg(&b)
+            );
+            assert_eq!(
+                corners(&a) == corners(&[]),
+                CubeState::default().apply_alg(&a) == CubeState::default()
+            );
+        }
+    }
+}
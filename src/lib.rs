@@ -1,3 +1,4 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(clippy::pedantic)]
 #![allow(
     clippy::enum_glob_use,
@@ -5,21 +6,44 @@
     clippy::return_self_not_must_use
 )]
 
+extern crate alloc;
+
+#[cfg(not(loom))]
+use core::cell::UnsafeCell;
+#[cfg(feature = "std")]
+use core::num::NonZero;
+#[cfg(all(debug_assertions, not(loom)))]
+use core::sync::atomic::AtomicBool;
+#[cfg(debug_assertions)]
+use core::sync::atomic::Ordering;
 use core::{
-    cell::UnsafeCell,
-    fmt::{self, Debug},
+    fmt::{self, Debug, Display},
     mem::MaybeUninit,
+    ops::RangeInclusive,
 };
 
+use alloc::{borrow::Cow, vec, vec::Vec};
+#[cfg(loom)]
+use loom::cell::UnsafeCell;
+#[cfg(all(debug_assertions, loom))]
+use loom::sync::atomic::AtomicBool;
+
 use crate::{
-    data::{AxialMove, Axis, Move, Rotation, Z4},
-    dp::DpArray,
+    data::{AxialMove, AxialRotation, Axis, Face, Move, Rotation, Z4},
+    dp::{DpArray, DpIndex},
 };
 
+pub mod alg;
+pub mod cube;
 pub mod data;
-mod dp;
+pub mod dp;
+pub mod facelets;
+pub mod solver;
+#[cfg(feature = "svg")]
+pub mod svg;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MoveOrRot {
     Move(Move),
     Rot(Rotation),
@@ -34,67 +58,302 @@ impl Debug for MoveOrRot {
     }
 }
 
+/// `MoveOrRot` with the rotations written out as `x`, `y` and `z`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MoveOrAxialRot {
+    Move(Move),
+    Rot(AxialRotation),
+}
+
+impl Debug for MoveOrAxialRot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Display for MoveOrAxialRot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Move(arg0) => Display::fmt(arg0, f),
+            Self::Rot(arg0) => Display::fmt(arg0, f),
+        }
+    }
+}
+
+/// How the length of a reconstruction is counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Metric {
+    /// Half turn metric: every turn counts as one move.
+    #[default]
+    Htm,
+    /// Quarter turn metric: half turns count as two moves.
+    Qtm,
+    /// Slice turn metric. The solver only ever emits outer turns, so this agrees with `Htm`.
+    Stm,
+}
+
+impl Metric {
+    pub const fn cost(self, by: Z4) -> usize {
+        match (self, by) {
+            (_, Z4::Zero) => 0,
+            (Self::Qtm, Z4::Two) => 2,
+            (Self::Htm | Self::Qtm | Self::Stm, _) => 1,
+        }
+    }
+}
+
+/// What a whole-cube rotation adds to the length, by the kind of rotation. Doing nothing is
+/// always free.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RotationCosts {
+    /// A quarter or half turn around one axis, like `y` or `x2`.
+    pub face: usize,
+    /// A half turn around an edge-to-edge diagonal, like `x y2`.
+    pub edge: usize,
+    /// A turn around a corner-to-corner diagonal, like `x y`.
+    pub corner: usize,
+}
+
+impl RotationCosts {
+    pub const fn flat(cost: usize) -> Self {
+        Self {
+            face: cost,
+            edge: cost,
+            corner: cost,
+        }
+    }
+
+    pub const fn cost(self, rot: Rotation) -> usize {
+        if rot.is_identity() {
+            0
+        } else if rot.is_face_rotation() {
+            self.face
+        } else if rot.is_180() {
+            self.edge
+        } else {
+            self.corner
+        }
+    }
+}
+
+impl Default for RotationCosts {
+    fn default() -> Self {
+        Self::flat(1)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolveOptions {
+    pub metric: Metric,
+    /// What a whole-cube rotation adds to the length. Set to `RotationCosts::flat(0)` to treat
+    /// rotations as free.
+    pub rotation_cost: RotationCosts,
+    /// The most rotations the reconstruction may contain, if limited.
+    pub max_rotations: Option<usize>,
+    /// Which faces may be turned, indexed by `Face as usize`.
+    pub allowed_faces: [bool; 6],
+    /// The axes whose faces may only be turned by half turns, indexed by `Axis::index`.
+    pub half_turn_axes: [bool; 3],
+    /// The longest reconstruction wanted, if limited. A choice that is already longer for some
+    /// part of the algorithm is dropped right away, as it can only get longer.
+    pub max_length: Option<usize>,
+    /// Break the remaining ties between equally good choices towards turning `R`, `U` and `F`
+    /// rather than `L`, `D` and `B`, as seen from how the cube is held for the turn. On by
+    /// default.
+    pub prefer_outer_faces: bool,
+}
+
+impl Default for SolveOptions {
+    fn default() -> Self {
+        Self {
+            metric: Metric::default(),
+            rotation_cost: RotationCosts::default(),
+            max_rotations: None,
+            allowed_faces: [true; 6],
+            half_turn_axes: [false; 3],
+            max_length: None,
+            prefer_outer_faces: true,
+        }
+    }
+}
+
+// A cell of the DP array, written through a shared reference so that the cells of a layer can be
+// filled from several threads at once. This is sound because of how `fill_intervals` (and
+// `fill_rayon`) use it:
+//
+// - every cell is written once, by one thread: a layer of size `sz` is split by `(l, rotation)`
+//   into disjoint items, each given to exactly one thread, and `fill_cells` writes the cells with
+//   that `(l, l + sz, rotation)`, each `(ax, budget)` once. `ZERO` is only visited on `X`, which is
+//   the only axis it is stored on
+// - cells are only read once their layer is done: `compute` only reads strictly smaller intervals,
+//   and the next layer is started only after the scope (or `for_each`) of this one has joined all
+//   its threads, which makes every write of this layer happen-before every read of the next
+// - nothing else touches the cells while a layer is being filled, as that needs `&mut Ctx`, and
+//   resetting them in `set_move` does too
+//
+// With `debug_assertions`, `Slot` checks the first two at run time, and `Ctx` checks the layers.
+// Built with `--cfg loom` and the `loom` feature, `Slot` is made of loom's cell and atomic instead,
+// so that the tests in `loom_tests` have loom check the second against every interleaving.
 struct Slot<T> {
     inner: UnsafeCell<MaybeUninit<T>>,
+    #[cfg(debug_assertions)]
+    init: AtomicBool,
 }
 
 impl<T> Default for Slot<T> {
     fn default() -> Self {
         Self {
             inner: UnsafeCell::new(MaybeUninit::uninit()),
+            #[cfg(debug_assertions)]
+            init: AtomicBool::new(false),
         }
     }
 }
 
 impl<T> Slot<T> {
     unsafe fn get(&self) -> &T {
-        unsafe { (&*self.inner.get()).assume_init_ref() }
+        #[cfg(debug_assertions)]
+        assert!(
+            self.init.load(Ordering::Acquire),
+            "Attempted to read a slot that was never written"
+        );
+
+        #[cfg(not(loom))]
+        let ptr = self.inner.get();
+        #[cfg(loom)]
+        let ptr = self.inner.with(|ptr| ptr);
+        unsafe { (&*ptr).assume_init_ref() }
     }
 
     unsafe fn set(&self, val: T) {
-        unsafe { &mut *self.inner.get() }.write(val);
+        #[cfg(debug_assertions)]
+        assert!(
+            !self.init.load(Ordering::Acquire),
+            "Attempted to write a slot twice"
+        );
+
+        #[cfg(not(loom))]
+        let ptr = self.inner.get();
+        #[cfg(loom)]
+        let ptr = self.inner.with_mut(|ptr| ptr);
+        unsafe { &mut *ptr }.write(val);
+
+        #[cfg(debug_assertions)]
+        self.init.store(true, Ordering::Release);
     }
 }
 
 unsafe impl<T: Send> Send for Slot<T> {}
-unsafe impl<T: Sync> Sync for Slot<T> {}
+// `set` moves a `T` in from whichever thread holds the `&Slot`, so sharing one needs `Send` too
+unsafe impl<T: Send + Sync> Sync for Slot<T> {}
 
-type Idx = (usize, usize, Rotation, AxialMove);
-type Res = usize;
+// the last component is the number of rotations still allowed. it is always `0` (and ignored) when
+// the number of rotations isn't limited
+type Idx = (usize, usize, Rotation, AxialMove, usize);
+// (length, number of rotations). Compared lexicographically, so that among equally long
+// reconstructions the one with the fewest rotations wins even when rotations are free.
+type Res = (usize, usize);
 type Reconstructed = Vec<MoveOrRot>;
-type DpChoice = (usize, Rotation, AxialMove);
+type DpChoice = (usize, Rotation, AxialMove, usize);
 type Val = Option<(Res, Option<DpChoice>)>;
-type Arr = DpArray<Slot<Val>, Idx>;
+type Arr<V> = DpArray<Slot<V>, Idx>;
+
+// what is kept in the DP array. reconstructing needs the choices, but the cost alone doesn't
+trait Cell: Copy {
+    fn from_val(val: Val) -> Self;
+
+    fn res(self) -> Option<Res>;
+}
+
+impl Cell for Val {
+    fn from_val(val: Val) -> Self {
+        val
+    }
+
+    fn res(self) -> Option<Res> {
+        self.map(|v| v.0)
+    }
+}
+
+impl Cell for Option<Res> {
+    fn from_val(val: Val) -> Self {
+        val.res()
+    }
+
+    fn res(self) -> Option<Res> {
+        self
+    }
+}
 
-struct Ctx<'a> {
-    alg: &'a [Move],
-    aux: Arr,
+struct Ctx<'a, V = Val> {
+    alg: Cow<'a, [Move]>,
+    options: SolveOptions,
+    aux: Arr<V>,
     #[cfg(debug_assertions)]
     up_to_sz: usize,
 }
 
-// TODO: some of these methods should be marked `unsafe` but aren't. eventually they should be made
-//       safe by adding checks (but only under cfg(debug_assertions))
-impl<'a> Ctx<'a> {
-    fn new(alg: &'a [Move]) -> Self {
-        let n = alg.len();
-        let aux = DpArray::new((n + 1, n + 1, (), ()));
+// `None` if the table would have more cells than fit in a `usize`, which no options should make
+// the solver try to allocate
+fn table_shape(n: usize, options: SolveOptions) -> Option<<Idx as DpIndex>::Runtime> {
+    let budgets = options
+        .max_rotations
+        .map_or(Some(1), |k| k.checked_add(1))?;
+    let shape = (n.checked_add(1)?, n.checked_add(1)?, (), (), budgets);
+    Idx::checked_size(&shape)?;
+    Some(shape)
+}
+
+// `get_full`, `get` and `set` are only sound as long as every cell is set exactly once, before it is
+// read. with `debug_assertions` both `Ctx` (the layer) and `Slot` (the cell) check this, so misuse
+// panics instead of being UB
+impl<'a, V: Cell> Ctx<'a, V> {
+    fn new(alg: impl Into<Cow<'a, [Move]>>, options: SolveOptions) -> Self {
+        let alg = alg.into();
+        let aux = DpArray::new(table_shape(alg.len(), options).expect("DP table too large"));
         Self {
             alg,
+            options,
             aux,
             #[cfg(debug_assertions)]
             up_to_sz: 0,
         }
     }
 
-    fn alg(&self) -> &'a [Move] {
-        self.alg
+    // starts over on `alg`, reusing the memory of the table when it's already big enough
+    fn reset(&mut self, alg: impl Into<Cow<'a, [Move]>>, options: SolveOptions) {
+        self.alg = alg.into();
+        self.options = options;
+        self.aux
+            .reset(table_shape(self.alg.len(), options).expect("DP table too large"));
+
+        #[cfg(debug_assertions)]
+        {
+            self.up_to_sz = 0;
+        }
+    }
+
+    fn alg(&self) -> &[Move] {
+        &self.alg
+    }
+
+    fn options(&self) -> SolveOptions {
+        self.options
     }
 
-    fn get_full(&self, idx: Idx) -> Val {
+    fn max_budget(&self) -> usize {
+        self.options.max_rotations.unwrap_or(0)
+    }
+
+    fn get_full(&self, idx: Idx) -> V {
         #[cfg(debug_assertions)]
         {
-            let (l, r, _, _) = idx;
+            assert!(
+                self.aux.try_get(&idx).is_some(),
+                "Attempted to access DP array out of range"
+            );
+            let (l, r, _, _, _) = idx;
             let sz = r - l;
             assert!(
                 sz < self.up_to_sz,
@@ -106,13 +365,23 @@ impl<'a> Ctx<'a> {
     }
 
     fn get(&self, idx: Idx) -> Option<Res> {
-        self.get_full(idx).map(|v| v.0)
+        self.get_full(idx).res()
+    }
+
+    // also `None` if `idx` is outside of the array. only for after `fill`, as the stage isn't
+    // checked
+    fn try_get(&self, idx: Idx) -> Option<Res> {
+        unsafe { self.aux.try_get(&idx)?.get() }.res()
     }
 
     fn set(&self, idx: Idx, val: Val) {
         #[cfg(debug_assertions)]
         {
-            let (l, r, _, _) = idx;
+            assert!(
+                self.aux.try_get(&idx).is_some(),
+                "Attempted to access DP array out of range"
+            );
+            let (l, r, _, _, _) = idx;
             let sz = r - l;
             assert!(
                 sz == self.up_to_sz,
@@ -120,7 +389,23 @@ impl<'a> Ctx<'a> {
             );
         }
 
-        unsafe { self.aux[idx].set(val) }
+        unsafe { self.aux[idx].set(V::from_val(val)) }
+    }
+
+    // replaces `alg[i]` and forgets every cell whose interval contains it, so they can be filled
+    // again from the layer of size `1`
+    fn set_move(&mut self, i: usize, mv: Move) {
+        self.alg.to_mut()[i] = mv;
+        for ((l, r, ..), slot) in self.aux.iter_mut() {
+            if l <= i && i < r {
+                *slot = Slot::default();
+            }
+        }
+
+        #[cfg(debug_assertions)]
+        {
+            self.up_to_sz = 0;
+        }
     }
 
     fn increment_sz(&mut self) {
@@ -131,34 +416,718 @@ impl<'a> Ctx<'a> {
     }
 }
 
+/// The shortest reconstruction of `alg`, rotations included.
+///
+/// An empty `alg` gives `Some(vec![])`. With the default options there always is a
+/// reconstruction, so `None` can only come from a bug in the solver, as for `try_solve_with`.
+/// Only `solve_with` can rule out every reconstruction, through `allowed_faces`, `half_turn_axes`,
+/// `max_rotations` or `max_length`.
+///
+/// The result is the same on every run and for any number of threads: each cell of the table is
+/// computed by a single thread, which tries the choices in a fixed order and keeps the first of
+/// the cheapest.
 pub fn solve(alg: &[Move]) -> Option<Reconstructed> {
+    solve_short(alg).or_else(|| solve_with(alg, SolveOptions::default()))
+}
+
+// one or two turns can only merge or cancel, which is what the table finds for them too, so
+// there's no need to fill it and spawn threads for that. `None` for anything longer
+fn solve_short(alg: &[Move]) -> Option<Reconstructed> {
+    let turns = match *alg {
+        [] => vec![],
+        [a] => vec![a],
+        // the turns on the same axis come out together, the positive face first
+        [a, b] if a.axis() == b.axis() => (AxialMove::from(a) + AxialMove::from(b))
+            .nonzero_moves()
+            .collect(),
+        [a, b] => vec![a, b],
+        _ => return None,
+    };
+    Some(
+        turns
+            .into_iter()
+            .filter(|mv| !mv.is_identity())
+            .map(MoveOrRot::Move)
+            .collect(),
+    )
+}
+
+pub fn solve_with_metric(alg: &[Move], metric: Metric) -> Option<Reconstructed> {
+    solve_with(
+        alg,
+        SolveOptions {
+            metric,
+            ..SolveOptions::default()
+        },
+    )
+}
+
+pub fn solve_with_rotation_costs(alg: &[Move], costs: RotationCosts) -> Option<Reconstructed> {
+    solve_with(
+        alg,
+        SolveOptions {
+            rotation_cost: costs,
+            ..SolveOptions::default()
+        },
+    )
+}
+
+pub fn solve_bounded_rotations(alg: &[Move], k: usize) -> Option<Reconstructed> {
+    solve_with(
+        alg,
+        SolveOptions {
+            max_rotations: Some(k),
+            ..SolveOptions::default()
+        },
+    )
+}
+
+/// The trade-off between moves and rotations: for each number of rotations, the fewest moves
+/// needed with at most that many, as `(moves, rotations, reconstruction)`. Only the counts that
+/// save moves over using fewer rotations are listed, from the fewest rotations up.
+///
+/// Rotating only changes which faces the turns are done on, never which of them merge or cancel,
+/// so the number of moves doesn't depend on the rotations and there is at most one entry: the
+/// shortest reconstruction with the fewest rotations. With every face allowed, that is one without
+/// any; see `solve_pareto_with` for how many rotations restricted options need.
+pub fn solve_pareto(alg: &[Move]) -> Vec<(usize, usize, Reconstructed)> {
+    solve_pareto_with(alg, SolveOptions::default())
+}
+
+/// `solve_pareto` under `options`, such as only some faces being allowed, where the cube has to be
+/// rotated to reach the others. Rotations are counted on their own, so `rotation_cost` and
+/// `max_rotations` are ignored.
+pub fn solve_pareto_with(
+    alg: &[Move],
+    options: SolveOptions,
+) -> Vec<(usize, usize, Reconstructed)> {
     let n = alg.len();
-    let mut ctx = Ctx::new(alg);
+    let free = SolveOptions {
+        rotation_cost: RotationCosts::flat(0),
+        max_rotations: None,
+        ..options
+    };
+    if table_shape(n, free).is_none() {
+        return vec![];
+    }
 
-    for sz in 0..=n {
-        std::thread::scope(|scope| {
-            for l in 0..=(n - sz) {
-                let r = l + sz;
-                for rotation in Rotation::ALL {
-                    let ctx = &ctx;
-                    scope.spawn(move || {
-                        for axis in [Axis::X, Axis::Y, Axis::Z] {
-                            for p in Z4::ALL {
-                                for n in Z4::ALL {
-                                    let ax = AxialMove::new(axis, p, n);
-                                    let idx = (l, r, rotation, ax);
-                                    ctx.set(idx, compute(ctx, idx));
-                                }
-                            }
+    // the fewest rotations of any shortest reconstruction. more than that never saves a move, so
+    // it's as many as the table needs to allow for
+    let mut ctx = Ctx::<Option<Res>>::new(alg, free);
+    fill(&mut ctx);
+    let Some((_, most)) = ctx.get((0, n, Rotation::ID, AxialMove::ZERO, 0)) else {
+        return vec![];
+    };
+
+    let mut ctx = Ctx::new(
+        alg,
+        SolveOptions {
+            max_rotations: Some(most),
+            ..free
+        },
+    );
+    fill(&mut ctx);
+
+    let mut front: Vec<(usize, usize, Reconstructed)> = vec![];
+    for budget in 0..=most {
+        let root = (0, n, Rotation::ID, AxialMove::ZERO, budget);
+        let Some((moves, rotations)) = ctx.get(root) else {
+            continue;
+        };
+        if front.last().is_some_and(|&(best, ..)| best <= moves) {
+            continue;
+        }
+        if let Some(recon) = reconstruct(&ctx, root) {
+            front.push((moves, rotations, recon));
+        }
+    }
+    front
+}
+
+pub fn solve_restricted(alg: &[Move], allowed: &[Face]) -> Option<Reconstructed> {
+    let mut allowed_faces = [false; 6];
+    for &face in allowed {
+        allowed_faces[face as usize] = true;
+    }
+
+    solve_with(
+        alg,
+        SolveOptions {
+            allowed_faces,
+            ..SolveOptions::default()
+        },
+    )
+}
+
+/// `solve`, but only if there's a reconstruction at most `k` long.
+pub fn solve_within(alg: &[Move], k: usize) -> Option<Reconstructed> {
+    solve_with(
+        alg,
+        SolveOptions {
+            max_length: Some(k),
+            ..SolveOptions::default()
+        },
+    )
+}
+
+/// `solve` within `<U, D, R2, L2, F2, B2>`: only `U` and `D` may be quarter turns.
+pub fn solve_domino(alg: &[Move]) -> Option<Reconstructed> {
+    solve_with(
+        alg,
+        SolveOptions {
+            half_turn_axes: [true, false, true],
+            ..SolveOptions::default()
+        },
+    )
+}
+
+pub fn solve_with(alg: &[Move], options: SolveOptions) -> Option<Reconstructed> {
+    try_solve_with(alg, options).ok()
+}
+
+/// Like `solve_with`, but with the reason when there's no reconstruction.
+///
+/// # Errors
+///
+/// `"DP table too large"` if the options, such as a huge `max_rotations`, make the table too big to
+/// even index, `"no reconstruction"` if the table has no entry for the whole of `alg`, and
+/// `"inconsistent DP table"` if it has one that can't be walked back. The last is always a bug in
+/// the solver.
+pub fn try_solve_with(alg: &[Move], options: SolveOptions) -> Result<Reconstructed, &'static str> {
+    let res = try_solve_ending(alg, options, Rotation::ID);
+    // doing every turn as it is, without any rotations, always works when nothing is ruled out
+    let unrestricted = options.allowed_faces == [true; 6]
+        && options.half_turn_axes == [false; 3]
+        && options.max_length.is_none();
+    debug_assert!(
+        !(unrestricted && matches!(res, Err("no reconstruction"))),
+        "The DP table has no reconstruction even though nothing is ruled out"
+    );
+    res
+}
+
+/// Bounds on the work a solve may take on, checked before anything is allocated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SolveLimits {
+    /// The longest algorithm accepted, if limited.
+    pub max_len: Option<usize>,
+    /// The most memory the DP table may take up, if limited.
+    pub max_memory_bytes: Option<usize>,
+}
+
+/// `try_solve_with` with the default options, but refusing anything over `limits` up front.
+///
+/// # Errors
+///
+/// `"algorithm too long"` or `"DP table too large"` for the limits, and otherwise the same as
+/// `try_solve_with`.
+pub fn solve_with_limits(alg: &[Move], limits: SolveLimits) -> Result<Reconstructed, &'static str> {
+    let options = SolveOptions::default();
+    if limits.max_len.is_some_and(|max| alg.len() > max) {
+        return Err("algorithm too long");
+    }
+    let stats = SolveStats::of(alg.len(), options);
+    if limits.max_memory_bytes.is_some_and(|max| stats.bytes > max) {
+        return Err("DP table too large");
+    }
+
+    try_solve_with(alg, options)
+}
+
+/// The size of the DP table behind a solve. All zero when no table was needed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SolveStats {
+    pub cells: usize,
+    /// `cells` times the size of a value, leaving out the checks debug builds keep next to each.
+    pub bytes: usize,
+    /// The interval sizes filled one after the other, from `0` to the length of the algorithm.
+    pub layers: usize,
+}
+
+impl SolveStats {
+    /// The stats for an algorithm of length `n`, without solving anything.
+    pub fn of(n: usize, options: SolveOptions) -> Self {
+        // as many as there is room for when they don't fit, which is too many either way
+        let cells = table_shape(n, options).map_or(usize::MAX, |shape| Idx::size(&shape));
+        Self::with_cells(cells, n)
+    }
+
+    fn with_cells(cells: usize, n: usize) -> Self {
+        Self {
+            cells,
+            bytes: cells.saturating_mul(size_of::<Val>()),
+            layers: n.saturating_add(1),
+        }
+    }
+}
+
+/// `solve`, along with the size of the table it filled, which is none for one or two turns.
+pub fn solve_with_stats(alg: &[Move]) -> (Option<Reconstructed>, SolveStats) {
+    if let Some(recon) = solve_short(alg) {
+        return (Some(recon), SolveStats::default());
+    }
+
+    let options = SolveOptions::default();
+    let n = alg.len();
+    if table_shape(n, options).is_none() {
+        return (None, SolveStats::of(n, options));
+    }
+    let mut ctx = Ctx::new(alg, options);
+    fill(&mut ctx);
+
+    // the same as `solve_with`
+    let root = (0, n, Rotation::ID, AxialMove::ZERO, ctx.max_budget());
+    let res = ctx.get(root).and_then(|_| reconstruct(&ctx, root));
+    (res, SolveStats::with_cells(ctx.aux.len(), n))
+}
+
+/// Like `solve`, but the reconstruction leaves the cube rotated by `rot` rather than back where it
+/// started. `Rotation::ID` gives the same as `solve`.
+pub fn solve_ending_orientation(alg: &[Move], rot: Rotation) -> Option<Reconstructed> {
+    try_solve_ending(alg, SolveOptions::default(), rot).ok()
+}
+
+/// `solve` for an `alg` written relative to the cube being held rotated by `rot0`. The
+/// reconstruction is relative to the unrotated cube, as if `alg` had been `flatten`ed after `rot0`.
+pub fn solve_from_orientation(alg: &[Move], rot0: Rotation) -> Option<Reconstructed> {
+    solve(&alg::rotate_alg(alg, -rot0))
+}
+
+fn try_solve_ending(
+    alg: &[Move],
+    options: SolveOptions,
+    ending: Rotation,
+) -> Result<Reconstructed, &'static str> {
+    let n = alg.len();
+    if table_shape(n, options).is_none() {
+        return Err("DP table too large");
+    }
+
+    // as seen from the cube held in `ending`, it starts out held in `-ending`
+    let mut ctx = if ending == Rotation::ID {
+        Ctx::new(alg, options)
+    } else {
+        Ctx::new(alg::rotate_alg(alg, ending), options)
+    };
+    fill(&mut ctx);
+
+    let root = (0, n, ending, AxialMove::ZERO, ctx.max_budget());
+    if ctx.get(root).is_none() {
+        return Err("no reconstruction");
+    }
+
+    let res = reconstruct(&ctx, root);
+    debug_assert!(
+        res.is_some(),
+        "The DP table has a cost for the whole algorithm but no reconstruction"
+    );
+    res.ok_or("inconsistent DP table")
+}
+
+/// Same as `solve`, but the layers are filled on rayon's global thread pool instead of spawning
+/// threads for each one.
+#[cfg(feature = "rayon")]
+pub fn solve_rayon(alg: &[Move]) -> Option<Reconstructed> {
+    let n = alg.len();
+    let mut ctx = Ctx::new(alg, SolveOptions::default());
+    fill_rayon(&mut ctx);
+
+    reconstruct(
+        &ctx,
+        (0, n, Rotation::ID, AxialMove::ZERO, ctx.max_budget()),
+    )
+}
+
+/// The rotation to hold the cube in before doing `alg` for the shortest reconstruction under
+/// `options`, and that reconstruction's length. Ties go to `Rotation::ID`, then to the first in
+/// `Rotation::ALL`. Holding the cube differently only relabels the faces, so this is always
+/// `Rotation::ID` unless `options` rule out some faces or turns.
+pub fn best_orientation(alg: &[Move], options: SolveOptions) -> Option<(Rotation, usize)> {
+    Rotation::ALL
+        .into_iter()
+        .filter_map(|rot| Some((rot, solve_cost_with(&alg::rotate_alg(alg, rot), options)?)))
+        .min_by_key(|&(rot, cost)| (cost, rot != Rotation::ID))
+}
+
+/// An approximate `solve` for algorithms too long for the full table. `alg` is cut every half a
+/// window, and each piece as well as each window of two pieces in a row is solved on its own. The
+/// result is the shortest way of putting those together, so a window across a seam is used
+/// wherever it does better than the two pieces on either side. Anything without a reconstruction
+/// is kept as it is.
+pub fn solve_tiled(alg: &[Move], window: usize) -> Reconstructed {
+    let tile = |start: usize, end: usize| {
+        let tile = &alg[start..end];
+        solve(tile).unwrap_or_else(|| {
+            tile.iter()
+                .filter(|mv| !mv.is_identity())
+                .map(|&mv| MoveOrRot::Move(mv))
+                .collect()
+        })
+    };
+
+    let half = (window / 2).max(1);
+    let steps: &[usize] = if 2 * half <= window { &[1, 2] } else { &[1] };
+    let cuts: Vec<usize> = (0..alg.len()).step_by(half).chain([alg.len()]).collect();
+
+    // for each cut, the length of the best reconstruction up to it, and the cut before it with
+    // the reconstruction in between
+    let mut best: Vec<(usize, usize, Reconstructed)> = vec![(0, 0, vec![])];
+    for i in 1..cuts.len() {
+        let here = steps
+            .iter()
+            .filter(|&&step| step <= i)
+            .map(|&step| {
+                let recon = tile(cuts[i - step], cuts[i]);
+                let len =
+                    best[i - step].0 + alg::len_in(&recon, Metric::Htm) + alg::rotations(&recon);
+                (len, i - step, recon)
+            })
+            .min_by_key(|&(len, ..)| len);
+        best.extend(here);
+    }
+
+    let mut pieces = vec![];
+    let mut i = cuts.len() - 1;
+    while i > 0 {
+        let (_, prev, recon) = core::mem::take(&mut best[i]);
+        pieces.push(recon);
+        i = prev;
+    }
+    pieces.into_iter().rev().flatten().collect()
+}
+
+/// Solves the turns between the rotations of `seq` on their own, keeping those rotations where they
+/// are. Unlike `solve`, which places rotations wherever they help, this only adds rotations inside
+/// the segments, and each segment's rotations cancel out before the next of the given ones.
+pub fn solve_segmented(seq: &[MoveOrRot]) -> Option<Reconstructed> {
+    let mut out = vec![];
+    let mut segment = vec![];
+    for &v in seq {
+        match v {
+            MoveOrRot::Move(mv) => segment.push(mv),
+            MoveOrRot::Rot(_) => {
+                out.extend(solve(&segment)?);
+                segment.clear();
+                out.push(v);
+            }
+        }
+    }
+    out.extend(solve(&segment)?);
+    Some(out)
+}
+
+/// Like `solve`, but for a sequence with rotations in it, such as the output of
+/// `alg::parse_extended` for wide and slice turns. The rotations are taken out by `alg::flatten`
+/// first, leaving the solver free to put its own wherever they help, and whatever `seq` turns the
+/// cube by overall is done at the end so that the two stay equivalent.
+pub fn solve_flattened(seq: &[MoveOrRot]) -> Option<Reconstructed> {
+    let mut out = solve(&alg::flatten(seq))?;
+    let net = alg::net_rotation(seq);
+    match out.last_mut() {
+        Some(MoveOrRot::Rot(last)) => {
+            *last = *last * net;
+            if *last == Rotation::ID {
+                out.pop();
+            }
+        }
+        _ if net != Rotation::ID => out.push(MoveOrRot::Rot(net)),
+        _ => {}
+    }
+    Some(out)
+}
+
+/// A reconstruction of the inverse of `alg`: what undoes `alg`, for learning a case backwards.
+pub fn solve_inverse(alg: &[Move]) -> Option<Reconstructed> {
+    solve(&alg::invert(alg))
+}
+
+/// `solve` for each of `algs`, in the same order. With the `rayon` feature the algorithms are
+/// solved in parallel on rayon's global thread pool, so no threads are spawned per algorithm.
+pub fn solve_many(algs: &[Vec<Move>]) -> Vec<Option<Reconstructed>> {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+
+        algs.par_iter().map(|alg| solve_rayon(alg)).collect()
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    {
+        algs.iter().map(|alg| solve(alg)).collect()
+    }
+}
+
+/// The length of the best reconstruction of `alg`, without the reconstruction itself. This needs
+/// less memory than `solve`, as the choices leading to each value aren't kept.
+pub fn solve_cost(alg: &[Move]) -> Option<usize> {
+    solve_cost_with(alg, SolveOptions::default())
+}
+
+pub fn solve_cost_with(alg: &[Move], options: SolveOptions) -> Option<usize> {
+    let n = alg.len();
+    table_shape(n, options)?;
+    let mut ctx = Ctx::<Option<Res>>::new(alg, options);
+    fill(&mut ctx);
+
+    ctx.get((0, n, Rotation::ID, AxialMove::ZERO, ctx.max_budget()))
+        .map(|(len, _)| len)
+}
+
+/// The filled DP table for `alg`, to look at the best cost of any interval in any context.
+pub struct SolveTable<'a> {
+    ctx: Ctx<'a, Option<Res>>,
+}
+
+impl SolveTable<'_> {
+    /// The best cost of `ax` followed by `alg[l..r]`, where the cube has to be turned by `rot`
+    /// overall and `ax` is done along with `alg[l]`. `None` if it's impossible or the interval is
+    /// out of bounds.
+    pub fn cost(&self, l: usize, r: usize, rot: Rotation, ax: AxialMove) -> Option<usize> {
+        // cells with `l > r` are never filled
+        if l > r {
+            return None;
+        }
+
+        self.ctx
+            .try_get((l, r, rot, ax, self.ctx.max_budget()))
+            .map(|(len, _)| len)
+    }
+}
+
+// the cost of every interval starting from the cube as it is held, as a grid with a row for each
+// `l` and a column for each `r`, and how many of all the cells have a cost
+impl Debug for SolveTable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let n = self.ctx.alg().len();
+        writeln!(f, "SolveTable of {n} moves")?;
+        #[cfg(debug_assertions)]
+        writeln!(f, "layers filled: {}", self.ctx.up_to_sz)?;
+
+        // the cells `fill_cells` fills: `ZERO` decodes the same from every axis, so only count it
+        // for `X`, where it's stored
+        let axials = AXIALS
+            .as_flattened()
+            .iter()
+            .enumerate()
+            .filter(|&(i, ax)| i == 0 || !ax.is_zero());
+        let (mut feasible, mut total) = (0, 0);
+        for l in 0..=n {
+            for r in l..=n {
+                for rot in Rotation::ALL {
+                    for (_, &ax) in axials.clone() {
+                        for budget in 0..=self.ctx.max_budget() {
+                            total += 1;
+                            let idx = (l, r, rot, ax, budget);
+                            feasible += usize::from(self.ctx.try_get(idx).is_some());
                         }
-                    });
+                    }
+                }
+            }
+        }
+        writeln!(f, "cells with a cost: {feasible} of {total}")?;
+
+        for l in 0..=n {
+            for r in 0..=n {
+                match self.cost(l, r, Rotation::ID, AxialMove::ZERO) {
+                    _ if r < l => write!(f, "    ")?,
+                    Some(cost) => write!(f, "{cost:>4}")?,
+                    None => write!(f, "   .")?,
                 }
             }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn solve_table(alg: &[Move]) -> SolveTable<'_> {
+    let mut ctx = Ctx::new(alg, SolveOptions::default());
+    fill(&mut ctx);
+
+    SolveTable { ctx }
+}
+
+/// A node of the tree of choices the solver made, as returned by `solve_tree`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChoiceNode {
+    /// The empty interval at `at`, where the cube is turned by `rot` (possibly `ID`).
+    Leaf { at: usize, rot: Rotation },
+    /// `alg[l..r]`, split into the cube turned by `r1` while `alg[l + 1..k]` cancels out, `f1`,
+    /// the turns done for `alg[l]` and whatever was carried along to it, and `alg[k..r]`.
+    /// `children` are the indices of the first and the last in the tree.
+    Split {
+        l: usize,
+        r: usize,
+        k: usize,
+        r1: Rotation,
+        f1: AxialMove,
+        children: [usize; 2],
+    },
+}
+
+/// How the solver split up an algorithm, with the root at index 0 and every node before its
+/// children.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChoiceTree {
+    nodes: Vec<ChoiceNode>,
+}
+
+impl ChoiceTree {
+    pub fn root(&self) -> &ChoiceNode {
+        &self.nodes[0]
+    }
+
+    pub fn nodes(&self) -> &[ChoiceNode] {
+        &self.nodes
+    }
+}
+
+/// The same moves as `solve`, in the same order, but only worked out from the DP table as they are
+/// iterated over. The table itself is still filled up front.
+pub fn solve_iter(alg: &[Move]) -> Option<impl Iterator<Item = MoveOrRot>> {
+    let n = alg.len();
+    let mut ctx = Ctx::new(alg, SolveOptions::default());
+    fill(&mut ctx);
+
+    let root = (0, n, Rotation::ID, AxialMove::ZERO, ctx.max_budget());
+    ctx.get(root)?;
+    Some(SolveIter {
+        ctx,
+        stack: vec![Todo::Cell(root, [None; 2])],
+    })
+}
+
+/// The choices behind `solve`, rather than the reconstruction they make up.
+pub fn solve_tree(alg: &[Move]) -> Option<ChoiceTree> {
+    let n = alg.len();
+    let mut ctx = Ctx::new(alg, SolveOptions::default());
+    fill(&mut ctx);
+
+    choice_tree(
+        &ctx,
+        (0, n, Rotation::ID, AxialMove::ZERO, ctx.max_budget()),
+    )
+}
+
+/// `solve`, with each turn paired with the index in `alg` it comes from. Inserted rotations come
+/// from no turn and get `None`, a turn merged from several of `alg` gets the last of them, and the
+/// turns of `alg` that are missing cancelled out.
+pub fn solve_with_origins(alg: &[Move]) -> Option<Vec<(MoveOrRot, Option<usize>)>> {
+    let n = alg.len();
+    let mut ctx = Ctx::new(alg, SolveOptions::default());
+    fill(&mut ctx);
+
+    let root = (0, n, Rotation::ID, AxialMove::ZERO, ctx.max_budget());
+    ctx.get(root)?;
+    reconstruct_with_origins(&ctx, root)
+}
+
+fn fill<V: Cell + Send + Sync>(ctx: &mut Ctx<'_, V>) {
+    let n = ctx.alg().len();
+    fill_intervals(ctx, |sz| 0..=n - sz);
+}
+
+// fills the cells of every interval containing `i`, which are the only ones to change with `alg[i]`
+fn refill<V: Cell + Send + Sync>(ctx: &mut Ctx<'_, V>, i: usize) {
+    let n = ctx.alg().len();
+    fill_intervals(ctx, |sz| (i + 1).saturating_sub(sz)..=i.min(n - sz));
+}
+
+// fills the intervals of each size starting at `lefts(size)`, smallest first
+#[cfg(feature = "std")]
+fn fill_intervals<V: Cell + Send + Sync>(
+    ctx: &mut Ctx<'_, V>,
+    lefts: impl Fn(usize) -> RangeInclusive<usize>,
+) {
+    let threads = std::thread::available_parallelism().map_or(1, NonZero::get);
+    fill_intervals_on(ctx, threads, lefts);
+}
+
+// `fill_intervals` spread over `threads` threads. With one, everything is filled on this thread
+#[cfg(feature = "std")]
+fn fill_intervals_on<V: Cell + Send + Sync>(
+    ctx: &mut Ctx<'_, V>,
+    threads: usize,
+    lefts: impl Fn(usize) -> RangeInclusive<usize>,
+) {
+    let n = ctx.alg().len();
+    for sz in 0..=n {
+        let items: Vec<_> = lefts(sz)
+            .flat_map(|l| Rotation::ALL.map(|rotation| (l, rotation)))
+            .collect();
+        if threads <= 1 {
+            for &(l, rotation) in &items {
+                fill_cells(ctx, l, l + sz, rotation);
+            }
+            ctx.increment_sz();
+            continue;
+        }
+
+        // one thread per chunk rather than per item, as the small layers have many tiny items
+        let chunk_size = items.len().div_ceil(threads).max(1);
+        std::thread::scope(|scope| {
+            for chunk in items.chunks(chunk_size) {
+                let ctx = &ctx;
+                scope.spawn(move || {
+                    for &(l, rotation) in chunk {
+                        fill_cells(ctx, l, l + sz, rotation);
+                    }
+                });
+            }
         });
         ctx.increment_sz();
     }
+}
 
-    reconstruct(&ctx, (0, n, Rotation::ID, AxialMove::ZERO))
+// without `std` there are no threads to spread the layers over
+#[cfg(not(feature = "std"))]
+fn fill_intervals<V: Cell>(ctx: &mut Ctx<'_, V>, lefts: impl Fn(usize) -> RangeInclusive<usize>) {
+    let n = ctx.alg().len();
+    for sz in 0..=n {
+        for l in lefts(sz) {
+            for rotation in Rotation::ALL {
+                fill_cells(ctx, l, l + sz, rotation);
+            }
+        }
+        ctx.increment_sz();
+    }
+}
+
+#[cfg(feature = "rayon")]
+fn fill_rayon<V: Cell + Send + Sync>(ctx: &mut Ctx<'_, V>) {
+    use rayon::prelude::*;
+
+    let n = ctx.alg().len();
+    for sz in 0..=n {
+        let ctx_ref = &*ctx;
+        (0..=(n - sz))
+            .into_par_iter()
+            .flat_map(|l| {
+                Rotation::ALL
+                    .into_par_iter()
+                    .map(move |rotation| (l, rotation))
+            })
+            .for_each(|(l, rotation)| fill_cells(ctx_ref, l, l + sz, rotation));
+        ctx.increment_sz();
+    }
+}
+
+fn fill_cells<V: Cell>(ctx: &Ctx<'_, V>, l: usize, r: usize, rotation: Rotation) {
+    for axis in Axis::ALL {
+        for p in Z4::ALL {
+            for n in Z4::ALL {
+                let ax = AxialMove::new(axis, p, n);
+                // `ZERO` is the same for every axis, only fill it once
+                if ax.is_zero() && axis != Axis::X {
+                    continue;
+                }
+                for budget in 0..=ctx.max_budget() {
+                    let idx = (l, r, rotation, ax, budget);
+                    ctx.set(idx, compute(ctx, idx));
+                }
+            }
+        }
+    }
 }
 
 enum BaseCase {
@@ -166,122 +1135,802 @@ enum BaseCase {
     Just(Rotation),
 }
 
-fn base_case(alg: &[Move], (l, r, rot, ax): Idx) -> Option<BaseCase> {
+fn base_case<V: Cell>(ctx: &Ctx<'_, V>, (l, r, rot, ax, budget): Idx) -> Option<BaseCase> {
+    let alg = ctx.alg();
     if l > r {
         return Some(BaseCase::Impossible);
     }
 
     if l == r {
-        return Some(if ax.is_zero() {
+        let over_budget =
+            ctx.options().max_rotations.is_some() && budget == 0 && rot != Rotation::ID;
+        return Some(if ax.is_zero() && !over_budget {
             BaseCase::Just(rot)
         } else {
             BaseCase::Impossible
         });
     }
 
-    if !ax.is_zero() && alg[l].axis() * rot != ax.axis() {
+    // what is carried in can only be done together with `alg[l]`
+    if !ax.is_zero() && alg[l].axis() != ax.axis() {
         return Some(BaseCase::Impossible);
     }
 
     None
 }
 
-fn compute(ctx: &Ctx<'_>, idx @ (l, r, _, _): Idx) -> Val {
-    match base_case(ctx.alg(), idx) {
+// `(l, r, r0, t0, budget)` is `t0` and then `alg[l..r]`, starting with the cube held in `-r0` and
+// ending with it back where it started, so that the cube is turned by `r0` overall. `t0` is in
+// terms of `alg` and still to be done along with `alg[l]`.
+//
+// a choice `(k, r1, t1, b1)` first turns the cube by `r1` while doing `alg[l + 1..k]`, which has to
+// cancel out, then does `f1`: what is left of `t0` and `alg[l]` after carrying `t1` on to
+// `alg[k]`, and then the rest from `k`. there are two kinds:
+//
+// - `k == l + 1`: the cube is turned by `r1` with `b1` of the budget, and `t1` is on the axis of
+//   `alg[l + 1]` if it is carried on at all
+// - `k > l + 1`: everything is carried over the stretch `alg[l + 1..k]` that cancels out, so
+//   `r1 == ID`, `b1 == 0` and `f1` is `ZERO`
+fn compute<V: Cell>(ctx: &Ctx<'_, V>, idx @ (l, r, _, t0, budget): Idx) -> Val {
+    match base_case(ctx, idx) {
         Some(BaseCase::Impossible) => return None,
-        Some(BaseCase::Just(rot)) => return Some((usize::from(rot != Rotation::ID), None)),
+        Some(BaseCase::Just(rot)) => {
+            let rotations = usize::from(rot != Rotation::ID);
+            let cost = ctx.options().rotation_cost.cost(rot);
+            return Some(((cost, rotations), None));
+        }
 
         None => {}
     }
 
+    let options = ctx.options();
+    let alg = ctx.alg();
+    let axis = alg[l].axis();
+    // `base_case` already rejected any non-zero `t0` on another axis
+    let u = t0.add(AxialMove::from(alg[l]))?;
+    let carries: &[AxialMove] = if l + 1 < r && alg[l + 1].axis() == axis {
+        &AXIALS[axis.index() as usize]
+    } else {
+        &[AxialMove::ZERO]
+    };
+    let is_outer =
+        |choice| apply_choice(alg, idx, choice).is_some_and(|(f1, ..)| f1.neg() == Z4::Zero);
+    let outer: Option<&dyn Fn(DpChoice) -> bool> = options.prefer_outer_faces.then_some(&is_outer);
+    let fits =
+        |new: Option<Res>| new.filter(|&(len, _)| options.max_length.is_none_or(|max| len <= max));
+
     let mut min = None;
-    for k in l + 1..=r {
-        for r1 in Rotation::ALL {
-            for t1_p in Z4::ALL {
-                for t1_n in Z4::ALL {
-                    let t1 = AxialMove::new((ctx.alg()[l] * r1).axis(), t1_p, t1_n);
-                    let choice = (k, r1, t1);
-                    let (f1, sub1, sub2) = apply_choice(ctx.alg(), idx, choice);
-                    let sub1 = ctx.get(sub1);
-                    let sub2 = ctx.get(sub2);
-                    let new = post_computation((f1, sub1, sub2));
-                    min_into(&mut min, new, choice);
-                }
+    for r1 in Rotation::ALL {
+        let b1 = usize::from(options.max_rotations.is_some() && r1 != Rotation::ID);
+        if b1 > budget {
+            continue;
+        }
+
+        let sub1 = ctx.get((l + 1, l + 1, r1, AxialMove::ZERO, b1));
+        for &t1 in carries {
+            let choice = (l + 1, r1, t1, b1);
+            let Some((f1, _, sub2)) = apply_choice(alg, idx, choice) else {
+                continue;
+            };
+            if !can_turn(options, f1) {
+                continue;
             }
+
+            let new = post_computation(options.metric, (f1, sub1, ctx.get(sub2)));
+            min_into(&mut min, fits(new), choice, outer);
         }
     }
 
+    for k in l + 2..r {
+        if alg[k].axis() != axis
+            || ctx.get((l + 1, k, Rotation::ID, AxialMove::ZERO, 0)) != Some((0, 0))
+        {
+            continue;
+        }
+
+        let choice = (k, Rotation::ID, u, 0);
+        let Some((f1, sub1, sub2)) = apply_choice(alg, idx, choice) else {
+            continue;
+        };
+        let new = post_computation(options.metric, (f1, ctx.get(sub1), ctx.get(sub2)));
+        min_into(&mut min, fits(new), choice, outer);
+    }
+
     min
 }
 
+// every axial move on each axis, `ZERO` first
+const AXIALS: [[AxialMove; 16]; 3] = {
+    let mut out = [[AxialMove::ZERO; 16]; 3];
+    let mut i = 0;
+    while i < 48 {
+        out[i / 16][i % 16] =
+            AxialMove::new(Axis::ALL[i / 16], Z4::ALL[i % 16 / 4], Z4::ALL[i % 4]);
+        i += 1;
+    }
+    out
+};
+
+// whether the options allow turning `f1`, as seen from how the cube is held
+fn can_turn(options: SolveOptions, f1: AxialMove) -> bool {
+    let half_turns_only = options.half_turn_axes[f1.axis().index() as usize];
+    f1.nonzero_moves().all(|mv| {
+        options.allowed_faces[mv.face() as usize] && (!half_turns_only || mv.by().is_even())
+    })
+}
+
 fn reconstruct(ctx: &Ctx<'_>, idx: Idx) -> Option<Reconstructed> {
-    match base_case(ctx.alg(), idx) {
+    Some(
+        reconstruct_with_origins(ctx, idx)?
+            .into_iter()
+            .map(|(v, _)| v)
+            .collect(),
+    )
+}
+
+fn reconstruct_with_origins(ctx: &Ctx<'_>, idx: Idx) -> Option<Vec<(MoveOrRot, Option<usize>)>> {
+    // walk over the choices with an explicit stack, as recursing goes as deep as the algorithm is
+    // long
+    let mut total = vec![];
+    let mut stack = vec![Todo::Cell(idx, [None; 2])];
+    while let Some(todo) = stack.pop() {
+        match todo {
+            Todo::Cell(idx, from) => reconstruct_step(ctx, idx, from, &mut stack)?,
+            Todo::Out(v, origin) => total.push((v, origin)),
+        }
+    }
+
+    Some(total)
+}
+
+// what is left to output while walking the table: a cell, with the index of `alg` the turns
+// carried into it come from (by whether their face is negative), or a step of the output
+enum Todo {
+    Cell(Idx, [Option<usize>; 2]),
+    Out(MoveOrRot, Option<usize>),
+}
+
+// pushes what `idx` outputs onto `stack`, in its place. `None` if the table can't be walked from
+// `idx`
+fn reconstruct_step(
+    ctx: &Ctx<'_>,
+    idx @ (l, _, r0, ..): Idx,
+    mut from: [Option<usize>; 2],
+    stack: &mut Vec<Todo>,
+) -> Option<()> {
+    match base_case(ctx, idx) {
         Some(BaseCase::Impossible) => return None,
         Some(BaseCase::Just(rot)) => {
-            return Some(if rot == Rotation::ID {
-                vec![]
-            } else {
-                vec![MoveOrRot::Rot(rot)]
-            });
+            if rot != Rotation::ID {
+                stack.push(Todo::Out(MoveOrRot::Rot(rot), None));
+            }
+            return Some(());
         }
         None => {}
     }
 
     let (_, choice) = ctx.get_full(idx)?;
-    let choice = choice.unwrap();
-    let (f1, sub1, sub2) = apply_choice(ctx.alg(), idx, choice);
-    let sub1 = reconstruct(ctx, sub1)?;
-    let sub2 = reconstruct(ctx, sub2)?;
-    Some(post_reconstruction((f1, sub1, sub2)))
-}
-
-fn apply_choice(alg: &[Move], (l, r, rot, ax): Idx, (k, r1, t1): DpChoice) -> (Move, Idx, Idx) {
-    let r0 = rot;
-    let t0 = ax;
-
-    let f1 = alg[l];
-    // let t2 = -AxialMove::from(f1 * r1) + AxialMove::from(alg[l] * r1) + t1;
-    let t2 = t1;
-
-    let sub1 = (l + 1, k, r1, t2);
-    let sub2 = (
-        k,
-        r,
-        -r1 * r0,
-        // t0 - ((AxialMove::from(alg[l] * r1) + t1) * (-r1 * r0)),
-        t0 - ((AxialMove::from(alg[l]) + (t1 * -r1)) * r0),
-    );
+    let choice @ (_, r1, ..) = choice?;
+    let (f1, sub1, sub2) = apply_choice(ctx.alg(), idx, choice)?;
+    let mv = ctx.alg()[l];
+    if !mv.is_identity() {
+        from[usize::from(mv.face().neg())] = Some(l);
+    }
 
-    (f1, sub1, sub2)
+    // output in the order `sub1`, `f1`, `sub2`, so they are pushed the other way round
+    stack.push(Todo::Cell(sub2, from));
+    let held = -r0 * r1;
+    for mv in post_reconstruction(f1).rev() {
+        let origin = from[usize::from((mv * -held).face().neg())];
+        stack.push(Todo::Out(MoveOrRot::Move(mv), origin));
+    }
+    stack.push(Todo::Cell(sub1, [None; 2]));
+    Some(())
 }
 
-fn post_computation((f1, sub1, sub2): (Move, Option<Res>, Option<Res>)) -> Option<Res> {
-    let (sub1, sub2) = (sub1?, sub2?);
+struct SolveIter<'a> {
+    ctx: Ctx<'a>,
+    stack: Vec<Todo>,
+}
 
-    let mut total = 0;
-    if f1.by() != Z4::Zero {
-        total += 1;
+impl Iterator for SolveIter<'_> {
+    type Item = MoveOrRot;
+
+    fn next(&mut self) -> Option<MoveOrRot> {
+        while let Some(todo) = self.stack.pop() {
+            let (idx, from) = match todo {
+                Todo::Cell(idx, from) => (idx, from),
+                Todo::Out(v, _) => return Some(v),
+            };
+            let step = reconstruct_step(&self.ctx, idx, from, &mut self.stack);
+            debug_assert!(
+                step.is_some(),
+                "The DP table has a cost for the whole algorithm but no reconstruction"
+            );
+            // as in `try_solve_with`, this can only be a bug in the solver, so it just ends here
+            if step.is_none() {
+                self.stack.clear();
+                return None;
+            }
+        }
+
+        None
     }
-    total += sub1;
-    total += sub2;
+}
+
+fn choice_tree(ctx: &Ctx<'_>, idx: Idx) -> Option<ChoiceTree> {
+    // the same walk as `reconstruct`, with each node linked into its parent once it's numbered
+    let mut nodes: Vec<ChoiceNode> = vec![];
+    let mut stack = vec![(idx, None)];
+    while let Some((idx @ (l, r, ..), parent)) = stack.pop() {
+        let id = nodes.len();
+        let node = match base_case(ctx, idx) {
+            Some(BaseCase::Impossible) => return None,
+            Some(BaseCase::Just(rot)) => ChoiceNode::Leaf { at: l, rot },
+            None => {
+                let (_, choice) = ctx.get_full(idx)?;
+                let choice @ (k, r1, ..) = choice?;
+                let (f1, sub1, sub2) = apply_choice(ctx.alg(), idx, choice)?;
+                stack.push((sub2, Some((id, 1))));
+                stack.push((sub1, Some((id, 0))));
+                ChoiceNode::Split {
+                    l,
+                    r,
+                    k,
+                    r1,
+                    f1,
+                    children: [0; 2],
+                }
+            }
+        };
+
+        if let Some((parent, i)) = parent
+            && let ChoiceNode::Split { children, .. } = &mut nodes[parent]
+        {
+            children[i] = id;
+        }
+        nodes.push(node);
+    }
+
+    Some(ChoiceTree { nodes })
+}
+
+// the turns `choice` does for `idx`, as seen from how the cube is held, and the two subproblems
+fn apply_choice(
+    alg: &[Move],
+    (l, r, r0, t0, budget): Idx,
+    (k, r1, t1, b1): DpChoice,
+) -> Option<(AxialMove, Idx, Idx)> {
+    let u = t0.add(AxialMove::from(alg[l]))?;
+    let f1 = u.add(-t1)? * (-r0 * r1);
+
+    let sub1 = (l + 1, k, r1, AxialMove::ZERO, b1);
+    let sub2 = (k, r, -r1 * r0, t1, budget.checked_sub(b1)?);
+    Some((f1, sub1, sub2))
+}
+
+fn post_computation(
+    metric: Metric,
+    (f1, sub1, sub2): (AxialMove, Option<Res>, Option<Res>),
+) -> Option<Res> {
+    let (sub1, sub2) = (sub1?, sub2?);
+
+    let mut total = (0, 0);
+    total.0 += metric.cost(f1.pos()) + metric.cost(f1.neg());
+    total.0 += sub1.0 + sub2.0;
+    total.1 += sub1.1 + sub2.1;
     Some(total)
 }
 
-fn min_into(min: &mut Val, new: Option<Res>, choice: DpChoice) {
+// ties keep the earlier choice, which `solve` relies on to be deterministic. given whether the
+// turns of a choice leave `L`, `D` and `B` alone, a tie first goes to a choice that does
+fn min_into(
+    min: &mut Val,
+    new: Option<Res>,
+    choice: DpChoice,
+    outer: Option<&dyn Fn(DpChoice) -> bool>,
+) {
+    let is_outer = |choice| outer.is_some_and(|outer| outer(choice));
     match (&mut *min, new) {
         (None, Some(v)) => *min = Some((v, Some(choice))),
         (Some(min), Some(v)) if v < min.0 => *min = (v, Some(choice)),
+        (Some(min), Some(v)) if v == min.0 && is_outer(choice) && !min.1.is_some_and(is_outer) => {
+            *min = (v, Some(choice));
+        }
         _ => {}
     }
 }
 
-fn post_reconstruction((f1, sub1, sub2): (Move, Reconstructed, Reconstructed)) -> Reconstructed {
-    let mut total = vec![];
-    if f1.by() != Z4::Zero {
-        total.push(MoveOrRot::Move(f1));
+fn post_reconstruction(f1: AxialMove) -> impl DoubleEndedIterator<Item = Move> {
+    let (a, b) = f1.moves();
+    [a, b].into_iter().filter(|mv| !mv.is_identity())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use super::*;
+    use crate::facelets::Facelets;
+
+    // the solver's own claim is checked against the facelets, which know nothing about it
+    fn assert_solves(alg: &[Move], recon: &[MoveOrRot]) {
+        assert_eq!(
+            alg::net_rotation(recon),
+            Rotation::ID,
+            "{alg:?} -> {recon:?}"
+        );
+        assert_eq!(
+            Facelets::default().apply_alg(alg).fingerprint(),
+            Facelets::default().apply_solution(recon).fingerprint(),
+            "{alg:?} -> {recon:?}"
+        );
+    }
+
+    #[test]
+    fn short_algorithms_match_the_table() {
+        let moves: Vec<_> = Face::ALL
+            .into_iter()
+            .flat_map(|face| Z4::ALL[1..].iter().map(move |&by| Move::new(face, by)))
+            .collect();
+        let algs = moves.iter().map(|&a| vec![a]).chain(
+            moves
+                .iter()
+                .flat_map(|&a| moves.iter().map(move |&b| vec![a, b])),
+        );
+        for alg in algs {
+            let fast = solve(&alg).unwrap();
+            assert_solves(&alg, &fast);
+            let table = solve_with(&alg, SolveOptions::default()).unwrap();
+            assert_eq!(format!("{fast:?}"), format!("{table:?}"), "{alg:?}");
+        }
+    }
+
+    #[test]
+    fn unrestricted_options_always_solve() {
+        let scramble = alg::random_scramble(4, 3);
+        for options in [
+            SolveOptions {
+                metric: Metric::Qtm,
+                ..SolveOptions::default()
+            },
+            SolveOptions {
+                rotation_cost: RotationCosts::flat(0),
+                ..SolveOptions::default()
+            },
+            SolveOptions {
+                max_rotations: Some(0),
+                ..SolveOptions::default()
+            },
+        ] {
+            assert_solves(&scramble, &try_solve_with(&scramble, options).unwrap());
+        }
+    }
+
+    #[test]
+    fn solve_flattened_keeps_the_rotations_of_slices_and_wide_turns() {
+        for s in ["R M", "r U r'", "M2 E"] {
+            let seq = alg::parse_extended(s).unwrap();
+            let recon = solve_flattened(&seq).unwrap();
+            assert_does(&seq, &recon);
+            assert_eq!(alg::net_rotation(&recon), alg::net_rotation(&seq), "{s}");
+        }
+    }
+
+    #[test]
+    fn solve_within_cuts_off_longer_reconstructions() {
+        let scramble = alg::random_scramble(4, 5);
+        let len = solve_cost(&scramble).unwrap();
+        assert_solves(&scramble, &solve_within(&scramble, len).unwrap());
+        assert!(solve_within(&scramble, len - 1).is_none());
+        assert!(
+            try_solve_with(
+                &[scramble[0]],
+                SolveOptions {
+                    max_length: Some(0),
+                    ..SolveOptions::default()
+                }
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn solve_tiled_merges_across_seams() {
+        // `U U'` crosses the first seam of tiles of 4 and `F F'` the first of tiles shifted by 2
+        let alg = alg::parse_groups("R D L U U' B R D L F F' B")
+            .unwrap()
+            .concat();
+        let recon = solve_tiled(&alg, 4);
+        assert_solves(&alg, &recon);
+        assert_eq!(alg::len_in(&recon, Metric::Htm), 8);
+
+        assert!(solve_tiled(&[], 4).is_empty());
+        assert_solves(&alg[..3], &solve_tiled(&alg[..3], 1));
+    }
+
+    #[test]
+    fn ties_go_to_the_outer_faces() {
+        let alg = alg::parse_groups("L R D' U").unwrap().concat();
+        let recon = solve(&alg).unwrap();
+        assert_eq!(alg::format_wca_solution(&recon), "R L U D'");
+
+        let options = SolveOptions {
+            prefer_outer_faces: false,
+            ..SolveOptions::default()
+        };
+        let recon = solve_with(&alg, options).unwrap();
+        assert_eq!(alg::format_wca_solution(&recon), "L R D' U");
+    }
+
+    #[test]
+    fn solves_are_deterministic() {
+        for scramble in scrambles() {
+            let recon = format!("{:?}", solve(&scramble).unwrap());
+            assert_eq!(format!("{:?}", solve(&scramble).unwrap()), recon);
+            #[cfg(feature = "rayon")]
+            assert_eq!(format!("{:?}", solve_rayon(&scramble).unwrap()), recon);
+        }
+
+        // with plenty of ties between rotations
+        let alg = alg::parse_groups("L R D R' L'").unwrap().concat();
+        let recon = solve_restricted(&alg, &[Face::R, Face::U, Face::F]).unwrap();
+        assert_eq!(alg::format_wca_solution(&recon), "R z y2 U R U' z y2 R'");
+    }
+
+    // the table filled on this thread alone gives the same as spread over several
+    #[cfg(feature = "std")]
+    #[test]
+    fn solves_the_same_on_any_number_of_threads() {
+        fn solve_on(alg: &[Move], options: SolveOptions, threads: usize) -> Option<Reconstructed> {
+            let n = alg.len();
+            let mut ctx = Ctx::new(alg, options);
+            fill_intervals_on(&mut ctx, threads, |sz| 0..=n - sz);
+            let root = (0, n, Rotation::ID, AxialMove::ZERO, ctx.max_budget());
+            ctx.get(root)?;
+            reconstruct(&ctx, root)
+        }
+
+        let ruf = SolveOptions {
+            allowed_faces: [true, true, true, false, false, false],
+            ..SolveOptions::default()
+        };
+        for scramble in scrambles() {
+            for options in [SolveOptions::default(), ruf] {
+                let serial = format!("{:?}", solve_on(&scramble, options, 1).unwrap());
+                assert_eq!(
+                    format!("{:?}", solve_with(&scramble, options).unwrap()),
+                    serial
+                );
+                assert_eq!(
+                    format!("{:?}", solve_on(&scramble, options, 3).unwrap()),
+                    serial
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn solve_table_counts_each_cell_once() {
+        let alg = alg::parse_groups("R U").unwrap().concat();
+        let table = solve_table(&alg);
+        // 6 intervals, 24 rotations and the 45 non-zero axial moves plus `ZERO`
+        let debug = format!("{table:?}");
+        assert!(debug.contains("of 6624\n"), "{debug}");
+        assert_eq!(table.cost(0, 2, Rotation::ID, AxialMove::ZERO), Some(2));
+        assert_eq!(table.cost(2, 1, Rotation::ID, AxialMove::ZERO), None);
     }
-    total.extend(sub1);
-    total.extend(sub2);
 
-    total
+    #[test]
+    fn rejects_tables_too_large_to_index() {
+        let options = SolveOptions {
+            max_rotations: Some(usize::MAX),
+            ..SolveOptions::default()
+        };
+        let alg = alg::random_scramble(3, 0);
+        assert_eq!(
+            try_solve_with(&alg, options).unwrap_err(),
+            "DP table too large"
+        );
+        assert_eq!(solve_cost_with(&alg, options), None);
+        assert_eq!(SolveStats::of(alg.len(), options).cells, usize::MAX);
+        assert_eq!(
+            SolveStats::of(usize::MAX, SolveOptions::default()).bytes,
+            usize::MAX
+        );
+    }
+
+    #[test]
+    fn solve_with_stats_reports_the_table_it_filled() {
+        let short = alg::parse_groups("R U").unwrap().concat();
+        let (recon, stats) = solve_with_stats(&short);
+        assert_eq!(format!("{recon:?}"), format!("{:?}", solve(&short)));
+        assert_eq!(stats, SolveStats::default());
+
+        let alg = alg::random_scramble(3, 0);
+        let (recon, stats) = solve_with_stats(&alg);
+        assert_eq!(format!("{recon:?}"), format!("{:?}", solve(&alg)));
+        assert_eq!(stats, SolveStats::of(3, SolveOptions::default()));
+        assert_eq!(stats.cells, 4 * 4 * 24 * 48);
+        assert_eq!(stats.bytes, stats.cells * size_of::<Val>());
+        assert_eq!(stats.layers, 4);
+    }
+
+    #[test]
+    fn best_orientation_helps_only_with_restrictions() {
+        let alg = alg::parse_groups("D B").unwrap().concat();
+        // every face allowed: the same cost however the cube is held
+        assert_eq!(
+            best_orientation(&alg, SolveOptions::default()),
+            Some((Rotation::ID, 2))
+        );
+
+        // with only `R`, `U` and `F`, holding it so that `D` and `B` are two of those does best
+        let ruf = SolveOptions {
+            allowed_faces: [true, true, true, false, false, false],
+            ..SolveOptions::default()
+        };
+        let (rot, cost) = best_orientation(&alg, ruf).unwrap();
+        assert_ne!(rot, Rotation::ID);
+        assert_eq!(cost, 2);
+        let rotated = alg::rotate_alg(&alg, rot);
+        assert!(
+            rotated
+                .iter()
+                .all(|mv| ruf.allowed_faces[mv.face() as usize]),
+            "{rotated:?}"
+        );
+        assert!(solve_cost_with(&alg, ruf).unwrap() > 2);
+    }
+
+    #[test]
+    fn solve_pareto_finds_the_fewest_rotations_needed() {
+        let alg = alg::parse_groups("L R D R' L'").unwrap().concat();
+        let ruf = SolveOptions {
+            allowed_faces: [true, true, true, false, false, false],
+            ..SolveOptions::default()
+        };
+        let front = solve_pareto_with(&alg, ruf);
+        let [(5, 2, recon)] = &front[..] else {
+            panic!("{front:?}");
+        };
+        assert_solves(&alg, recon);
+
+        // one rotation is too few, and more can't make it any shorter
+        let at_most = |k| SolveOptions {
+            max_rotations: Some(k),
+            rotation_cost: RotationCosts::flat(0),
+            ..ruf
+        };
+        assert_eq!(solve_cost_with(&alg, at_most(1)), None);
+        assert_eq!(solve_cost_with(&alg, at_most(4)), Some(5));
+    }
+
+    #[test]
+    fn solves_long_algorithms() {
+        // undoing itself, which makes the reconstruction walk through every carry at once
+        let scramble = alg::random_scramble(10, 9);
+        let alg = [scramble.clone(), alg::invert(&scramble)].concat();
+        assert!(solve(&alg).unwrap().is_empty());
+
+        let long = alg::random_scramble(1000, 9);
+        assert_solves(&long, &solve_tiled(&long, 2));
+
+        let stats = SolveStats::of(10_000, SolveOptions::default());
+        assert_eq!(stats.cells, 10_001 * 10_001 * 24 * 48);
+        assert_eq!(stats.layers, 10_001);
+    }
+
+    // no choice in the table should ever fail on axes that don't line up
+    #[cfg(feature = "quickcheck")]
+    #[test]
+    fn solves_arbitrary_algorithms() {
+        #[expect(
+            clippy::needless_pass_by_value,
+            reason = "quickcheck hands over owned values"
+        )]
+        fn prop(alg: Vec<Move>) -> bool {
+            let alg = &alg[..alg.len().min(4)];
+            solve(alg).is_some_and(|recon| alg::is_equivalent(alg, &recon))
+        }
+        quickcheck::QuickCheck::new()
+            .tests(30)
+            .quickcheck(prop as fn(Vec<Move>) -> bool);
+    }
+
+    #[test]
+    fn solves_random_scrambles() {
+        // every position up to two face turns from solved, by how many, found without the table
+        let moves: Vec<_> = Face::ALL
+            .into_iter()
+            .flat_map(|face| Z4::ALL[1..].iter().map(move |&by| Move::new(face, by)))
+            .collect();
+        let mut by_distance = vec![vec![Facelets::default()]];
+        for _ in 0..2 {
+            let next = by_distance
+                .last()
+                .unwrap()
+                .iter()
+                .flat_map(|state| moves.iter().map(|&mv| state.apply_move(mv)));
+            by_distance.push(next.collect());
+        }
+
+        for (seed, len) in (0..16).zip((1..=5).cycle()) {
+            let scramble = alg::random_scramble(len, seed);
+            let recon = solve(&scramble).unwrap();
+            assert_solves(&scramble, &recon);
+            assert!(alg::solves(&alg::invert(&scramble), &recon));
+
+            // with every face allowed, rotating never helps, and the only turns to save are the
+            // ones that merge or cancel
+            let htm = alg::len_in(&recon, Metric::Htm);
+            assert_eq!(alg::rotations(&recon), 0, "{recon:?}");
+            assert_eq!(htm, alg::normalize(&scramble).len(), "{recon:?}");
+
+            // and nothing shorter does the same, which short ones can be checked against directly
+            let state = Facelets::default().apply_alg(&scramble);
+            match by_distance
+                .iter()
+                .position(|states| states.contains(&state))
+            {
+                Some(optimum) => assert_eq!(htm, optimum, "{recon:?}"),
+                None => assert!(htm >= by_distance.len(), "{recon:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn solves_with_cancellations() {
+        for seed in 0..8 {
+            let a = alg::random_scramble(2, seed);
+            let b = alg::random_scramble(2, seed + 100);
+            let alg = [a.clone(), b, alg::invert(&a)].concat();
+            assert_solves(&alg, &solve(&alg).unwrap());
+
+            let undone = [a.clone(), alg::invert(&a)].concat();
+            assert!(solve(&undone).unwrap().is_empty());
+        }
+    }
+
+    #[test]
+    fn solves_empty_and_single_moves() {
+        assert!(solve(&[]).unwrap().is_empty());
+        assert!(solve_with(&[], SolveOptions::default()).unwrap().is_empty());
+        assert!(solve_bounded_rotations(&[], 0).unwrap().is_empty());
+
+        for face in Face::ALL {
+            for by in Z4::ALL {
+                let mv = Move::new(face, by);
+                let recon = solve(&[mv]).unwrap();
+                assert_solves(&[mv], &recon);
+                let expected = if mv.is_identity() {
+                    ""
+                } else {
+                    &format!("{mv}")
+                };
+                assert_eq!(alg::format_wca_solution(&recon), expected);
+            }
+        }
+    }
+
+    // the same as `assert_solves`, for a sequence with rotations in it
+    fn assert_does(seq: &[MoveOrRot], recon: &[MoveOrRot]) {
+        assert_eq!(
+            Facelets::default().apply_solution(seq).fingerprint(),
+            Facelets::default().apply_solution(recon).fingerprint(),
+            "{seq:?} -> {recon:?}"
+        );
+    }
+
+    fn scrambles() -> impl Iterator<Item = Vec<Move>> {
+        (0..6).zip((2..=4).cycle()).map(|(seed, len)| {
+            let mut scramble = alg::random_scramble(len, seed);
+            // something for the solver to cancel
+            scramble.push(scramble[0].inv());
+            scramble
+        })
+    }
+
+    #[test]
+    fn solve_iter_and_tree_agree_with_solve() {
+        // the turns and rotations of `tree` in the order `reconstruct` outputs them
+        fn walk(tree: &ChoiceTree, i: usize, out: &mut Vec<MoveOrRot>) {
+            match tree.nodes()[i] {
+                ChoiceNode::Leaf { rot, .. } => {
+                    out.extend((rot != Rotation::ID).then_some(MoveOrRot::Rot(rot)));
+                }
+                ChoiceNode::Split { f1, children, .. } => {
+                    walk(tree, children[0], out);
+                    out.extend(f1.nonzero_moves().map(MoveOrRot::Move));
+                    walk(tree, children[1], out);
+                }
+            }
+        }
+
+        for scramble in scrambles() {
+            let recon = format!("{:?}", solve(&scramble).unwrap());
+            let iter: Vec<_> = solve_iter(&scramble).unwrap().collect();
+            assert_eq!(format!("{iter:?}"), recon);
+
+            let tree = solve_tree(&scramble).unwrap();
+            let mut walked = vec![];
+            walk(&tree, 0, &mut walked);
+            assert_eq!(format!("{walked:?}"), recon);
+
+            let origins = solve_with_origins(&scramble).unwrap();
+            let turns: Vec<_> = origins.iter().map(|&(v, _)| v).collect();
+            assert_eq!(format!("{turns:?}"), recon);
+            for (v, origin) in origins {
+                assert_eq!(matches!(v, MoveOrRot::Move(_)), origin.is_some());
+                assert!(origin.is_none_or(|i| i < scramble.len()));
+            }
+        }
+    }
+
+    #[test]
+    fn solve_variants_do_what_the_algorithm_does() {
+        for scramble in scrambles() {
+            let seq: Vec<_> = scramble.iter().map(|&mv| MoveOrRot::Move(mv)).collect();
+            assert_solves(&alg::invert(&scramble), &solve_inverse(&scramble).unwrap());
+            assert_solves(&scramble, &solve_tiled(&scramble, 3));
+
+            let ending = Rotation::ALL[5];
+            let mut recon = solve_ending_orientation(&scramble, ending).unwrap();
+            assert_eq!(alg::net_rotation(&recon), ending);
+            recon.push(MoveOrRot::Rot(-ending));
+            assert_solves(&scramble, &recon);
+
+            let mut held = vec![MoveOrRot::Rot(ending)];
+            held.extend(&seq);
+            held.push(MoveOrRot::Rot(-ending));
+            assert_does(&held, &solve_from_orientation(&scramble, ending).unwrap());
+
+            let mut segmented = seq.clone();
+            segmented.insert(2, MoveOrRot::Rot(ending));
+            assert_does(&segmented, &solve_segmented(&segmented).unwrap());
+            assert_does(&segmented, &solve_flattened(&segmented).unwrap());
+        }
+
+        let scramble = alg::random_scramble(3, 0);
+        let [(moves, 0, recon)] = &solve_pareto(&scramble)[..] else {
+            panic!("more than one reconstruction, or some rotations, with every face allowed");
+        };
+        assert_solves(&scramble, recon);
+        assert_eq!(alg::len_in(recon, Metric::Htm), *moves);
+
+        let algs: Vec<_> = scrambles().collect();
+        for (scramble, recon) in algs.iter().zip(solve_many(&algs)) {
+            assert_solves(scramble, &recon.unwrap());
+        }
+    }
+}
+
+// run with `RUSTFLAGS="--cfg loom" cargo test --release --features loom --lib loom`, as everything
+// else would have to run inside `loom::model` too. without `--cfg loom` these still pass, but loom
+// only sees its threads and not the accesses to the cells
+#[cfg(all(test, feature = "loom"))]
+mod loom_tests {
+    use loom::{sync::Arc, thread};
+
+    use super::*;
+
+    // two threads each write a cell of the same layer, and the cells are read once both are
+    // joined, which is all that orders the reads after the writes in `fill_intervals`
+    #[test]
+    fn slots_written_on_other_threads_are_read_after_join() {
+        loom::model(|| {
+            let slots: Arc<[Slot<usize>; 2]> = Arc::default();
+            let handles: Vec<_> = (0..2)
+                .map(|i| {
+                    let slots = Arc::clone(&slots);
+                    thread::spawn(move || unsafe { slots[i].set(i + 1) })
+                })
+                .collect();
+            for handle in handles {
+                handle.join().unwrap();
+            }
+
+            assert_eq!(unsafe { (*slots[0].get(), *slots[1].get()) }, (1, 2));
+        });
+    }
 }
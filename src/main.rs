@@ -1,5 +1,5 @@
-use itertools::{Either, Itertools};
-use rkt_solver::{MoveOrRot, data::Move, solve};
+use itertools::Itertools;
+use rkt_solver::{alg::expand_rotations, data::Move, solve};
 
 fn main() {
     let val = std::env::args().nth(1).unwrap();
@@ -9,19 +9,7 @@ fn main() {
         .collect::<Vec<_>>();
     let result = solve(&alg);
     if let Some(result) = result {
-        println!(
-            "{}",
-            result
-                .into_iter()
-                .flat_map(|v| {
-                    match v {
-                        MoveOrRot::Move(mv) => Either::Left(core::iter::once(mv)),
-                        MoveOrRot::Rot(rot) => Either::Right(rot.to_axials()),
-                    }
-                    .factor_into_iter()
-                })
-                .format(" ")
-        );
+        println!("{}", expand_rotations(&result).into_iter().format(" "));
     } else {
         println!("None");
     }
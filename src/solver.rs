@@ -0,0 +1,194 @@
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use crate::{
+    Ctx, Reconstructed, SolveOptions,
+    data::{AxialMove, Move, Rotation},
+    fill, reconstruct, refill,
+};
+#[cfg(feature = "std")]
+use crate::{alg::normalize, table_shape};
+
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+/// Remembers the reconstructions it has found. Algorithms are `normalize`d before looking them up,
+/// so `R R` and `R2` share an entry, and the reconstruction returned is the one for the normalized
+/// algorithm. The DP table is kept between solves too, so it's only reallocated when a longer
+/// algorithm comes along. Needs the `std` feature.
+#[cfg(feature = "std")]
+#[derive(Default)]
+pub struct Solver {
+    options: SolveOptions,
+    cache: HashMap<Vec<Move>, Option<Reconstructed>>,
+    stats: CacheStats,
+    table: Option<Ctx<'static>>,
+}
+
+#[cfg(feature = "std")]
+impl Solver {
+    pub fn new(options: SolveOptions) -> Self {
+        Self {
+            options,
+            ..Self::default()
+        }
+    }
+
+    pub fn solve(&mut self, alg: &[Move]) -> Option<Reconstructed> {
+        let alg = normalize(alg);
+        if let Some(res) = self.cache.get(&alg) {
+            self.stats.hits += 1;
+            return res.clone();
+        }
+
+        self.stats.misses += 1;
+        // the same as `solve_with`, which gives up on a table too large to index
+        table_shape(alg.len(), self.options)?;
+        let ctx = self
+            .table
+            .get_or_insert_with(|| Ctx::new(Vec::new(), self.options));
+        ctx.reset(alg.clone(), self.options);
+        fill(ctx);
+
+        // the same as `solve_with`
+        let root = (
+            0,
+            alg.len(),
+            Rotation::ID,
+            AxialMove::ZERO,
+            ctx.max_budget(),
+        );
+        let res = ctx.get(root).and_then(|_| reconstruct(ctx, root));
+        self.cache.insert(alg, res.clone());
+        res
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.stats = CacheStats::default();
+    }
+}
+
+/// Keeps the DP table of one algorithm, so that changing a single move only recomputes the
+/// intervals containing it rather than the whole table.
+pub struct IncrementalSolver {
+    ctx: Ctx<'static>,
+}
+
+impl IncrementalSolver {
+    /// # Panics
+    ///
+    /// If `options` make the table too large to index, as for a huge `max_rotations`.
+    pub fn new(alg: Vec<Move>, options: SolveOptions) -> Self {
+        let mut ctx = Ctx::new(alg, options);
+        fill(&mut ctx);
+        Self { ctx }
+    }
+
+    pub fn alg(&self) -> &[Move] {
+        self.ctx.alg()
+    }
+
+    /// The same as `solve_with` on the current algorithm.
+    pub fn solve(&self) -> Option<Reconstructed> {
+        let n = self.ctx.alg().len();
+        let root = (0, n, Rotation::ID, AxialMove::ZERO, self.ctx.max_budget());
+        self.ctx.get(root)?;
+        reconstruct(&self.ctx, root)
+    }
+
+    /// Replaces the move at `i` with `mv`.
+    ///
+    /// # Panics
+    ///
+    /// If `i` is out of bounds.
+    pub fn update_move(&mut self, i: usize, mv: Move) {
+        if self.ctx.alg()[i] == mv {
+            return;
+        }
+
+        self.ctx.set_move(i, mv);
+        refill(&mut self.ctx, i);
+    }
+
+    /// Replaces the whole algorithm, starting over if its length changed.
+    pub fn set_alg(&mut self, alg: Vec<Move>) {
+        if alg.len() != self.ctx.alg().len() {
+            self.ctx.reset(alg, self.ctx.options());
+            fill(&mut self.ctx);
+            return;
+        }
+
+        for (i, mv) in alg.into_iter().enumerate() {
+            self.update_move(i, mv);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use super::*;
+    use crate::{alg, solve};
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn solver_solves_and_caches() {
+        let mut solver = Solver::new(SolveOptions::default());
+        for seed in 0..4 {
+            let scramble = alg::random_scramble(4, seed);
+            let recon = solver.solve(&scramble).unwrap();
+            assert!(alg::is_equivalent(&scramble, &recon));
+            let normalized = solve(&normalize(&scramble)).unwrap();
+            assert_eq!(format!("{recon:?}"), format!("{normalized:?}"));
+        }
+
+        // `R R` is normalized to `R2`, which `solve` has to do too
+        let scramble = alg::random_scramble(2, 0);
+        let mut doubled = scramble.clone();
+        doubled.push(scramble[1]);
+        assert!(alg::is_equivalent(
+            &doubled,
+            &solver.solve(&doubled).unwrap()
+        ));
+        assert_eq!(solver.stats().misses, 5);
+        solver.solve(&alg::random_scramble(4, 0));
+        assert_eq!(solver.stats().hits, 1);
+    }
+
+    #[test]
+    fn incremental_solver_agrees_with_solve() {
+        let scramble = alg::random_scramble(4, 1);
+        let mut solver = IncrementalSolver::new(scramble.clone(), SolveOptions::default());
+        assert!(alg::is_equivalent(&scramble, &solver.solve().unwrap()));
+
+        let mut changed = scramble.clone();
+        for (i, mv) in [(1, scramble[0]), (3, scramble[2].inv()), (0, scramble[3])] {
+            changed[i] = mv;
+            solver.update_move(i, mv);
+            let recon = solver.solve().unwrap();
+            assert!(
+                alg::is_equivalent(&changed, &recon),
+                "{changed:?} -> {recon:?}"
+            );
+            assert_eq!(
+                format!("{recon:?}"),
+                format!("{:?}", solve(&changed).unwrap())
+            );
+        }
+
+        let longer = alg::random_scramble(5, 2);
+        solver.set_alg(longer.clone());
+        assert!(alg::is_equivalent(&longer, &solver.solve().unwrap()));
+    }
+}
@@ -0,0 +1,62 @@
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::{
+    data::{Color, ColorScheme, Face},
+    facelets::Facelets,
+};
+
+// the side of a sticker, in SVG user units
+const STICKER: usize = 20;
+
+const fn hex(color: Color) -> &'static str {
+    match color {
+        Color::White => "#ffffff",
+        Color::Yellow => "#ffd500",
+        Color::Green => "#009b48",
+        Color::Blue => "#0045ad",
+        Color::Red => "#b71234",
+        Color::Orange => "#ff5800",
+    }
+}
+
+// where each face goes in the net, in stickers from the top left, laid out like `Facelets`'s
+// `Display`
+const fn origin(face: Face) -> (usize, usize) {
+    match face {
+        Face::U => (3, 0),
+        Face::L => (0, 3),
+        Face::F => (3, 3),
+        Face::R => (6, 3),
+        Face::B => (9, 3),
+        Face::D => (3, 6),
+    }
+}
+
+/// `state` as an SVG of the unfolded net, coloured by `scheme`.
+pub fn to_svg(state: &Facelets, scheme: ColorScheme) -> String {
+    let mut out = String::new();
+    // writing to a `String` never fails
+    let _ = write!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}">"#,
+        12 * STICKER,
+        9 * STICKER,
+    );
+    for face in Face::ALL {
+        let (x, y) = origin(face);
+        for row in 0..3 {
+            for col in 0..3 {
+                let _ = write!(
+                    out,
+                    r#"<rect x="{}" y="{}" width="{STICKER}" height="{STICKER}" fill="{}" stroke="black"/>"#,
+                    (x + col) * STICKER,
+                    (y + row) * STICKER,
+                    hex(state.get(face, row, col).color(scheme)),
+                );
+            }
+        }
+    }
+    out.push_str("</svg>");
+    out
+}